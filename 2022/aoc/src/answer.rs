@@ -0,0 +1,176 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The answer to one part of a puzzle.
+///
+/// Answers compare equal when their normalized forms are equal, so an integer matches the same
+/// integer of any width, strings match regardless of surrounding whitespace, and glyphs match
+/// whether they were drawn with `#` and `.`, or with `█` and spaces.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Integer(i128),
+    String(String),
+    /// A picture drawn in a grid of characters, like the letters on day 10's CRT. One string per
+    /// row.
+    Glyphs(Vec<String>),
+    /// There is no answer for the input, like a path to a square that can't be reached. Parts
+    /// that return `None` get this.
+    None,
+}
+
+impl Answer {
+    /// Make a glyph answer from text with one line per row of the picture.
+    pub fn glyphs(text: &str) -> Answer {
+        Answer::Glyphs(text.lines().map(String::from).collect())
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Glyphs(rows) if rows.len() > 1)
+    }
+
+    /// The answer in a canonical form for comparison. Glyphs are drawn with `#` for lit pixels
+    /// and `.` for dark ones, without any trailing dark pixels.
+    pub fn normalized(&self) -> String {
+        match self {
+            Answer::Integer(value) => value.to_string(),
+            Answer::String(value) => value.trim().to_string(),
+            Answer::Glyphs(rows) => rows
+                .iter()
+                .map(|row| {
+                    let normalized_row: String = row
+                        .chars()
+                        .map(|c| if is_lit_pixel(c) { '#' } else { '.' })
+                        .collect();
+                    normalized_row.trim_end_matches('.').to_string()
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+            Answer::None => NO_ANSWER.to_string(),
+        }
+    }
+}
+
+/// How [Answer::None] is shown, and read back.
+const NO_ANSWER: &str = "none";
+
+fn is_lit_pixel(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::String(value) => write!(f, "{}", value),
+            Answer::Glyphs(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::None => write!(f, "{}", NO_ANSWER),
+        }
+    }
+}
+
+/// Read an answer back from its displayed form. Whole numbers become integers, and text with more
+/// than one line becomes glyphs.
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_matches('\n');
+        if let Ok(value) = s.trim().parse::<i128>() {
+            Ok(Answer::Integer(value))
+        } else if s.trim() == NO_ANSWER {
+            Ok(Answer::None)
+        } else if s.contains('\n') {
+            Ok(Answer::glyphs(s))
+        } else {
+            Ok(Answer::String(s.trim().to_string()))
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Answer {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::String(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        Answer::String(value.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Answer {
+        value.map_or(Answer::None, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+
+    #[test]
+    fn integers_of_different_widths_are_equal() {
+        assert!(Answer::from(70374u32) == Answer::from(70374usize));
+        assert!(Answer::from(-3i32) == Answer::from(-3i64));
+        assert!(Answer::from(14561971968u64) == "14561971968".parse().unwrap());
+    }
+
+    #[test]
+    fn strings_ignore_surrounding_whitespace() {
+        assert!(Answer::from("FCVRLMVQP") == Answer::from(" FCVRLMVQP\n"));
+        assert!(Answer::from("FCVRLMVQP") != Answer::from("RWLWGJGFD"));
+    }
+
+    #[test]
+    fn glyphs_ignore_pixel_characters() {
+        let dots = Answer::glyphs("##..\n#..#\n");
+        let blocks = Answer::glyphs("██\n█  █");
+        assert!(dots == blocks);
+        assert!(dots.normalized() == "##\n#..#");
+        assert!(dots != Answer::glyphs("##..\n#..."));
+    }
+
+    #[test]
+    fn missing_answers_are_none() {
+        assert!(Answer::from(Some(394u32)) == Answer::from(394));
+        assert!(matches!(Answer::from(None::<u32>), Answer::None));
+        assert!(Answer::None != Answer::from(0));
+    }
+
+    #[test]
+    fn displayed_answers_parse_back() {
+        for answer in [
+            Answer::from(13920),
+            Answer::from("RWLWGJGFD"),
+            Answer::glyphs("##..\n#..#"),
+            Answer::from(None::<u32>),
+        ] {
+            let parsed: Answer = answer.to_string().parse().unwrap();
+            assert!(parsed == answer);
+            assert!(parsed.is_multiline() == answer.is_multiline());
+        }
+    }
+}
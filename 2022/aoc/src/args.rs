@@ -0,0 +1,47 @@
+/// Take an option with a value, given as `--name VALUE` or `--name=VALUE`, out of the command line
+/// arguments, leaving the rest of them.
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let prefix = format!("{}=", name);
+    let Some(i) = args
+        .iter()
+        .position(|arg| arg == name || arg.starts_with(&prefix))
+    else {
+        return Ok(None);
+    };
+
+    let option = args.remove(i);
+    match option.strip_prefix(&prefix) {
+        Some(value) => Ok(Some(value.to_string())),
+        None if i < args.len() => Ok(Some(args.remove(i))),
+        None => Err(format!("Missing a value after {}", name)),
+    }
+}
+
+/// Take a flag without a value, like `--cached`, out of the command line arguments. Returns
+/// whether it was there.
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let length = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != length
+}
+
+#[cfg(test)]
+mod test {
+    use super::{take_flag, take_option};
+
+    #[test]
+    fn options_are_taken_with_their_values() {
+        let mut args: Vec<String> = ["1", "--input", "-", "--example=small", "--all"]
+            .map(String::from)
+            .to_vec();
+
+        assert!(take_option(&mut args, "--input") == Ok(Some("-".to_string())));
+        assert!(take_option(&mut args, "--example") == Ok(Some("small".to_string())));
+        assert!(take_option(&mut args, "--format") == Ok(None));
+        assert!(take_flag(&mut args, "--all"));
+        assert!(!take_flag(&mut args, "--all"));
+        assert!(args == vec!["1"]);
+
+        assert!(take_option(&mut vec!["--input".to_string()], "--input").is_err());
+    }
+}
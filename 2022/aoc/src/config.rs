@@ -0,0 +1,150 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The environment variable that names the config file, instead of the usual place for it.
+pub const CONFIG_VARIABLE: &str = "AOC_CONFIG";
+
+/// Settings from the config file.
+///
+/// The file has a `key = value` setting on each line. Blank lines and lines starting with `#` are
+/// ignored.
+///
+/// ```text
+/// # Where the dayNN directories with inputs and answers live.
+/// data_dir = ~/advent-of-code/2022
+/// # The session cookie from the site, for downloading inputs.
+/// session = 53616c7465645f5f...
+/// # The key that encrypted inputs are decrypted with, instead of input.key next to this file.
+/// key_file = ~/.secrets/aoc-input.key
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Config {
+    settings: Vec<(String, String)>,
+    /// The directory holding the config file, which relative paths in it are relative to.
+    directory: Option<PathBuf>,
+}
+
+impl Config {
+    /// Where the config file is: the file named by `AOC_CONFIG`, or `aoc/config` in the user's
+    /// config directory.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os(CONFIG_VARIABLE) {
+            return Some(PathBuf::from(path));
+        }
+
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|config_home| config_home.join("aoc").join("config"))
+    }
+
+    /// Read the config file. It's fine for there not to be one, which is the same as an empty one.
+    pub fn load() -> Result<Config, String> {
+        match Config::path() {
+            Some(path) => Config::read(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Config, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(format!("Unable to read {}: {}", path.display(), e)),
+        };
+
+        let mut config: Config = contents
+            .parse()
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        config.directory = path.parent().map(Path::to_path_buf);
+        Ok(config)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.settings
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// A setting that's a path. `~` at the start stands for the home directory, and relative paths
+    /// are relative to the directory the config file is in.
+    pub fn get_path(&self, key: &str) -> Option<PathBuf> {
+        let value = self.get(key)?;
+
+        let path = match value.strip_prefix("~/") {
+            Some(rest) => match env::var_os("HOME") {
+                Some(home) => Path::new(&home).join(rest),
+                None => PathBuf::from(value),
+            },
+            None => PathBuf::from(value),
+        };
+
+        match &self.directory {
+            Some(directory) if path.is_relative() => Some(directory.join(path)),
+            _ => Some(path),
+        }
+    }
+}
+
+impl FromStr for Config {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut settings: Vec<(String, String)> = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected 'key = value'", i + 1));
+            };
+            let key = key.trim().to_string();
+            if settings.iter().any(|(k, _)| *k == key) {
+                return Err(format!("line {}: {} is set more than once", i + 1, key));
+            }
+            settings.push((key, value.trim().to_string()));
+        }
+
+        Ok(Config {
+            settings,
+            directory: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Config;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn settings_are_read_from_lines() {
+        let config: Config = "# A comment\n\ndata_dir = /srv/aoc\nsession=abc=def\n"
+            .parse()
+            .unwrap();
+        assert!(config.get("data_dir") == Some("/srv/aoc"));
+        assert!(config.get("session") == Some("abc=def"));
+        assert!(config.get("missing").is_none());
+
+        assert!("data_dir /srv/aoc".parse::<Config>().is_err());
+        assert!("a = 1\na = 2".parse::<Config>().is_err());
+    }
+
+    #[test]
+    fn relative_paths_are_relative_to_the_config_file() {
+        let mut config: Config = "data_dir = inputs\nabsolute = /srv/aoc".parse().unwrap();
+        config.directory = Some(PathBuf::from("/home/elf/.config/aoc"));
+
+        assert!(
+            config.get_path("data_dir") == Some(Path::new("/home/elf/.config/aoc/inputs").into())
+        );
+        assert!(config.get_path("absolute") == Some(Path::new("/srv/aoc").into()));
+    }
+}
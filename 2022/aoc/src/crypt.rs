@@ -0,0 +1,231 @@
+use crate::Config;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// The environment variable holding the key for encrypted inputs, written in hex.
+pub const KEY_VARIABLE: &str = "AOC_INPUT_KEY";
+
+/// The extension added to the name of a file when it's encrypted.
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// What every encrypted file starts with, so a file encrypted some other way is recognised.
+const MAGIC: &[u8] = b"aoc-enc1";
+
+const NONCE_SIZE: usize = 12;
+
+/// The key that puzzle inputs are encrypted with, so they can be committed without being
+/// redistributed.
+///
+/// The key is the first of
+///
+/// 1. the `AOC_INPUT_KEY` environment variable,
+/// 2. the file named by the `key_file` setting in the [Config] file,
+/// 3. `input.key` next to the config file.
+///
+/// Key files hold the key in hex, like the environment variable.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// A new random key.
+    pub fn generate() -> Key {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Key, String> {
+        let hex = hex.trim();
+        let error = || "A key is 64 hex digits".to_string();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(error());
+        }
+
+        let mut key = [0; 32];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| error())?;
+        }
+        Ok(Key(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Where the key file is, whether or not there is one.
+    pub fn path() -> Result<Option<PathBuf>, String> {
+        if let Some(path) = Config::load()?.get_path("key_file") {
+            return Ok(Some(path));
+        }
+        Ok(Config::path().map(|config_path| config_path.with_file_name("input.key")))
+    }
+
+    /// The key from the environment or the key file.
+    pub fn load() -> Result<Key, String> {
+        if let Some(hex) = env::var_os(KEY_VARIABLE) {
+            return Key::from_hex(&hex.to_string_lossy())
+                .map_err(|e| format!("{} is not a key: {}", KEY_VARIABLE, e));
+        }
+
+        let Some(path) = Key::path()? else {
+            return Err(format!("There's no key: set {}", KEY_VARIABLE));
+        };
+        match fs::read_to_string(&path) {
+            Ok(hex) => Key::from_hex(&hex).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(format!(
+                "There's no key: set {} or put one in {}",
+                KEY_VARIABLE,
+                path.display()
+            )),
+            Err(e) => Err(format!("Unable to read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// The key, making a new key file if there's no key yet. The path of the new key file is
+    /// returned with it, as it needs to be kept safe: inputs can't be decrypted without it.
+    pub fn load_or_create() -> Result<(Key, Option<PathBuf>), String> {
+        if env::var_os(KEY_VARIABLE).is_some() {
+            return Ok((Key::load()?, None));
+        }
+
+        let Some(path) = Key::path()? else {
+            return Err(format!("There's no key: set {}", KEY_VARIABLE));
+        };
+        if path.exists() {
+            return Ok((Key::load()?, None));
+        }
+
+        let key = Key::generate();
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .map_err(|e| format!("Unable to create {}: {}", directory.display(), e))?;
+        }
+        write_private(&path, format!("{}\n", key.to_hex()).as_bytes())
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+        Ok((key, Some(path)))
+    }
+
+    /// Encrypt some text, with a new random nonce each time.
+    pub fn encrypt(&self, plaintext: &str) -> Vec<u8> {
+        let cipher = ChaCha20Poly1305::new(&self.0.into());
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .expect("encrypting in memory can't fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Decrypt what [Key::encrypt] made. This fails if it was encrypted with another key or has
+    /// been changed since.
+    pub fn decrypt(&self, encrypted: &[u8]) -> Result<String, String> {
+        let Some(rest) = encrypted.strip_prefix(MAGIC) else {
+            return Err("It isn't an encrypted input".to_string());
+        };
+        if rest.len() < NONCE_SIZE {
+            return Err("It's cut short".to_string());
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+
+        let cipher = ChaCha20Poly1305::new(&self.0.into());
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                "It was encrypted with a different key, or has been changed".to_string()
+            })?;
+        String::from_utf8(plaintext).map_err(|_| "It isn't text".to_string())
+    }
+}
+
+/// The name a file has once it's encrypted, which is its name with `.enc` on the end.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(name)
+}
+
+pub fn is_encrypted_path(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == ENCRYPTED_EXTENSION)
+}
+
+/// Open an input file to be read a line at a time, like [read_input] finds it. Encrypted inputs
+/// are decrypted in memory first, since they can only be checked once they've been read whole.
+pub fn open_input(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if is_encrypted_path(path) || (!path.exists() && encrypted_path(path).is_file()) {
+        return Ok(Box::new(io::Cursor::new(read_input(path)?.into_bytes())));
+    }
+    Ok(Box::new(BufReader::new(fs::File::open(path)?)))
+}
+
+/// Read an input file, decrypting it in memory if it's encrypted. If there's no file at `path` but
+/// there's an encrypted one with the same name, that's read instead, so `input.txt` finds
+/// `input.txt.enc`.
+pub fn read_input(path: &Path) -> io::Result<String> {
+    let encrypted = if is_encrypted_path(path) {
+        path.to_path_buf()
+    } else {
+        match fs::read_to_string(path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound && encrypted_path(path).is_file() => {
+                encrypted_path(path)
+            }
+            result => return result,
+        }
+    };
+
+    let contents = fs::read(&encrypted)?;
+    Key::load()
+        .and_then(|key| key.decrypt(&contents))
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unable to decrypt {}: {}", encrypted.display(), e),
+            )
+        })
+}
+
+/// Write a file only its owner can read, as key files should be.
+fn write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    use std::io::Write;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encrypted_path, Key};
+    use std::path::Path;
+
+    #[test]
+    fn inputs_are_encrypted_and_decrypted() {
+        let key = Key::generate();
+        let input = "1000\n2000\n\n3000\n";
+
+        let encrypted = key.encrypt(input);
+        assert!(!encrypted.windows(4).any(|w| w == b"1000"));
+        assert!(key.decrypt(&encrypted).as_deref() == Ok(input));
+        // A new nonce each time.
+        assert!(key.encrypt(input) != encrypted);
+
+        assert!(Key::generate().decrypt(&encrypted).is_err());
+        let mut changed = encrypted.clone();
+        *changed.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt(&changed).is_err());
+        assert!(key.decrypt(input.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn keys_are_written_in_hex() {
+        let key = Key::generate();
+        assert!(Key::from_hex(&format!("{}\n", key.to_hex())) == Ok(key));
+        assert!(Key::from_hex("00ff").is_err());
+        assert!(Key::from_hex(&"g".repeat(64)).is_err());
+        assert!(encrypted_path(Path::new("day01/input.txt")) == Path::new("day01/input.txt.enc"));
+    }
+}
@@ -0,0 +1,284 @@
+use crate::Config;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where the puzzles are.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// The site asks automated tools to say what they are, so it can get in touch about problems.
+const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (puzzle input fetcher, caches every input)"
+);
+
+/// Why a puzzle input couldn't be fetched.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The session token wasn't accepted, or the input belongs to someone else.
+    BadSession,
+    /// The puzzle doesn't exist or hasn't unlocked yet.
+    NotAvailable,
+    /// Too many requests were made recently. The server may say how long to wait.
+    RateLimited(Option<Duration>),
+    /// Any other response, with its status and the start of the body.
+    Status(u16, String),
+    /// The server couldn't be reached, or the connection broke.
+    Connection(String),
+    /// The input couldn't be saved in the cache.
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::BadSession => write!(
+                f,
+                "The session token was rejected. Log in again and copy the new session cookie \
+                 into the config file"
+            ),
+            FetchError::NotAvailable => write!(f, "The puzzle isn't available yet"),
+            FetchError::RateLimited(Some(wait)) => write!(
+                f,
+                "Too many requests, try again in {} seconds",
+                wait.as_secs()
+            ),
+            FetchError::RateLimited(None) => write!(f, "Too many requests, try again later"),
+            FetchError::Status(status, body) => {
+                write!(f, "The server responded with {}: {}", status, body)
+            }
+            FetchError::Connection(message) => write!(f, "Unable to connect: {}", message),
+            FetchError::Io(path, e) => write!(f, "Unable to write {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Whether [Fetcher::fetch] had to download an input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Downloads puzzle inputs with a session token, which is the `session` cookie of someone logged in
+/// to the site.
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    /// A fetcher with the `session` token from the [Config] file.
+    pub fn from_config(config: &Config) -> Result<Fetcher, String> {
+        match config.get("session") {
+            Some(session) if !session.is_empty() => Ok(Fetcher::new(BASE_URL, session)),
+            _ => Err(format!(
+                "There's no session token in the config file{}. Add 'session = ...' with the \
+                 session cookie from the site",
+                Config::path()
+                    .map(|path| format!(" ({})", path.display()))
+                    .unwrap_or_default()
+            )),
+        }
+    }
+
+    pub fn input_url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    /// Download the input for a day's puzzle.
+    pub fn download(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        let response = self
+            .agent
+            .get(&self.input_url(year, day))
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| FetchError::Connection(e.to_string())),
+            Err(ureq::Error::Status(status, response)) => Err(match status {
+                // The site answers a missing or wrong token with a 400 and a request to log in.
+                400 | 401 | 403 => FetchError::BadSession,
+                404 => FetchError::NotAvailable,
+                429 => FetchError::RateLimited(
+                    response
+                        .header("Retry-After")
+                        .and_then(|seconds| seconds.trim().parse().ok())
+                        .map(Duration::from_secs),
+                ),
+                _ => {
+                    let body = response.into_string().unwrap_or_default();
+                    let first_line = body.lines().next().unwrap_or_default();
+                    FetchError::Status(status, first_line.chars().take(200).collect())
+                }
+            }),
+            Err(ureq::Error::Transport(transport)) => {
+                Err(FetchError::Connection(transport.to_string()))
+            }
+        }
+    }
+
+    /// Make sure the input for a day's puzzle is at `path`, downloading it only if it isn't there
+    /// already, as it is or encrypted. Inputs never change, so a saved one is never downloaded
+    /// again.
+    pub fn fetch(&self, year: u32, day: u32, path: &Path) -> Result<Fetched, FetchError> {
+        if path.is_file() || crate::crypt::encrypted_path(path).is_file() {
+            return Ok(Fetched::Cached);
+        }
+
+        let input = self.download(year, day)?;
+
+        // Write to a temporary file first, so a half-written input is never taken for a saved one.
+        let io_error = |e| FetchError::Io(path.to_path_buf(), e);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(io_error)?;
+        }
+        let partial = path.with_extension("partial");
+        fs::write(&partial, input).map_err(io_error)?;
+        fs::rename(&partial, path).map_err(io_error)?;
+
+        Ok(Fetched::Downloaded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FetchError, Fetched, Fetcher};
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// A stand-in for the site, answering like it does. Day 1 is an input, day 2 is rate limited,
+    /// day 3 isn't out yet and day 4 is broken. Only the session `good` is accepted. Returns the
+    /// base URL and a count of the requests made.
+    fn serve() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut cookie = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.strip_prefix("Cookie: ") {
+                        cookie = value.trim().to_string();
+                    }
+                }
+
+                let (status, extra, body) = if cookie != "session=good" {
+                    (
+                        "400 Bad Request",
+                        "",
+                        "Puzzle inputs differ by user.  Please log in.",
+                    )
+                } else {
+                    match request_line.split(' ').nth(1).unwrap_or_default() {
+                        "/2022/day/1/input" => ("200 OK", "", "1000\n2000\n\n3000\n"),
+                        "/2022/day/2/input" => ("429 Too Many Requests", "Retry-After: 90\r\n", ""),
+                        "/2022/day/4/input" => ("500 Internal Server Error", "", "Oops\nmore"),
+                        _ => ("404 Not Found", "", "Not found"),
+                    }
+                };
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    extra,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    #[test]
+    fn inputs_are_downloaded_once() {
+        let (base_url, requests) = serve();
+        let fetcher = Fetcher::new(&base_url, "good");
+
+        let directory = std::env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+        let path = directory.join("day01").join("input.txt");
+
+        assert!(matches!(
+            fetcher.fetch(2022, 1, &path),
+            Ok(Fetched::Downloaded)
+        ));
+        assert!(fs::read_to_string(&path).unwrap() == "1000\n2000\n\n3000\n");
+        assert!(matches!(fetcher.fetch(2022, 1, &path), Ok(Fetched::Cached)));
+        assert!(requests.load(Ordering::SeqCst) == 1);
+
+        // An encrypted input counts as saved too.
+        let path = directory.join("day02").join("input.txt");
+        fs::create_dir_all(directory.join("day02")).unwrap();
+        fs::write(directory.join("day02").join("input.txt.enc"), "").unwrap();
+        assert!(matches!(fetcher.fetch(2022, 2, &path), Ok(Fetched::Cached)));
+        assert!(requests.load(Ordering::SeqCst) == 1);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn error_responses_are_explained() {
+        let (base_url, _) = serve();
+        let fetcher = Fetcher::new(&base_url, "good");
+
+        assert!(matches!(
+            fetcher.download(2022, 2),
+            Err(FetchError::RateLimited(Some(wait))) if wait == Duration::from_secs(90)
+        ));
+        assert!(matches!(
+            fetcher.download(2022, 3),
+            Err(FetchError::NotAvailable)
+        ));
+        assert!(matches!(
+            fetcher.download(2022, 4),
+            Err(FetchError::Status(500, body)) if body == "Oops"
+        ));
+        assert!(matches!(
+            Fetcher::new(&base_url, "bad").download(2022, 1),
+            Err(FetchError::BadSession)
+        ));
+
+        // Nothing is listening on port 1.
+        assert!(matches!(
+            Fetcher::new("http://127.0.0.1:1", "good").download(2022, 1),
+            Err(FetchError::Connection(_))
+        ));
+    }
+}
@@ -0,0 +1,172 @@
+use std::io::{self, Read};
+
+/// A small, fast random number generator (SplitMix64) that gives the same numbers for the same
+/// seed everywhere, for making up puzzle inputs that can be made again.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `low` up to and including `high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "{} is more than {}", low, high);
+        match (high - low).checked_add(1) {
+            Some(count) => low + self.next_u64() % count,
+            None => self.next_u64(),
+        }
+    }
+
+    /// One of the items, or `None` if there aren't any.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.between(0, items.len() as u64 - 1) as usize)
+    }
+
+    /// Put the items in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.between(0, i as u64) as usize);
+        }
+    }
+}
+
+/// A way of making up random inputs of one shape for a day's puzzle, to try a solution on more
+/// than the real input and the examples.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    /// What the runner's `gen` command calls the shape, like `random` or `deep`.
+    pub shape: &'static str,
+    pub description: &'static str,
+    generate: fn(&mut Rng, usize, &mut String),
+}
+
+impl Generator {
+    /// `generate` adds an input of about the size it's given, in bytes, to the string.
+    pub fn new(
+        shape: &'static str,
+        description: &'static str,
+        generate: fn(&mut Rng, usize, &mut String),
+    ) -> Generator {
+        Generator {
+            shape,
+            description,
+            generate,
+        }
+    }
+
+    /// An input of about `size` bytes, which is the same every time for the same seed and size.
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        let mut input = String::with_capacity(size);
+        (self.generate)(&mut Rng::new(seed), size, &mut input);
+        input
+    }
+}
+
+/// An input that's made up a piece at a time as it's read, so inputs far larger than memory can
+/// be streamed through a solution.
+///
+/// `next_piece` adds the next piece of text to the string it's given, which is empty each time.
+/// Pieces are made until there are at least `size` bytes, so the input ends after a whole piece.
+pub struct Generated<F> {
+    remaining: u64,
+    piece: String,
+    position: usize,
+    next_piece: F,
+}
+
+impl<F: FnMut(&mut String)> Generated<F> {
+    pub fn new(size: u64, next_piece: F) -> Generated<F> {
+        Generated {
+            remaining: size,
+            piece: String::new(),
+            position: 0,
+            next_piece,
+        }
+    }
+}
+
+impl<F: FnMut(&mut String)> Read for Generated<F> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.piece.len() {
+            if self.remaining == 0 {
+                return Ok(0);
+            }
+            self.piece.clear();
+            self.position = 0;
+            (self.next_piece)(&mut self.piece);
+            if self.piece.is_empty() {
+                return Err(io::Error::other("The generator made an empty piece"));
+            }
+            self.remaining = self.remaining.saturating_sub(self.piece.len() as u64);
+        }
+
+        let piece = &self.piece.as_bytes()[self.position..];
+        let length = piece.len().min(buffer.len());
+        buffer[..length].copy_from_slice(&piece[..length]);
+        self.position += length;
+        Ok(length)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Generated, Generator, Rng};
+    use std::io::Read;
+
+    #[test]
+    fn random_numbers_are_repeatable() {
+        let mut rng = Rng::new(2022);
+        let numbers: Vec<u64> = (0..5).map(|_| rng.between(1, 6)).collect();
+        assert!(numbers.iter().all(|n| (1..=6).contains(n)));
+
+        let mut again = Rng::new(2022);
+        assert!(numbers == (0..5).map(|_| again.between(1, 6)).collect::<Vec<u64>>());
+        assert!(Rng::new(0).next_u64() == 0xe220a8397b1dcdaf);
+        assert!(again.choose::<u8>(&[]).is_none());
+
+        let mut items = [1, 2, 3, 4, 5];
+        again.shuffle(&mut items);
+        items.sort();
+        assert!(items == [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn inputs_are_made_up_to_their_size() {
+        let mut n = 0;
+        let mut input = String::new();
+        Generated::new(10, |piece: &mut String| {
+            n += 1;
+            piece.push_str(&format!("{}\n", n));
+        })
+        .read_to_string(&mut input)
+        .unwrap();
+        assert!(input == "1\n2\n3\n4\n5\n");
+    }
+
+    #[test]
+    fn generators_repeat_their_inputs() {
+        let generator = Generator::new("digits", "Random digits", |rng, size, input| {
+            for _ in 0..size {
+                input.push_str(&rng.between(0, 9).to_string());
+            }
+        });
+        assert!(generator.generate(1, 20).len() == 20);
+        assert!(generator.generate(1, 20) == generator.generate(1, 20));
+        assert!(generator.generate(1, 20) != generator.generate(2, 20));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::args::take_option;
+use crate::crypt;
+use crate::{Config, Day};
+use std::env;
+use std::fmt;
+use std::io::{self, BufRead, Read};
+use std::path::{Path, PathBuf};
+
+/// The environment variable that sets the data directory.
+pub const DATA_DIR_VARIABLE: &str = "AOC_DATA_DIR";
+
+/// The name of the puzzle input in each day's directory.
+pub const INPUT_FILENAME: &str = "input.txt";
+
+/// Where a day's puzzle input comes from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// The whole input.
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => crypt::read_input(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// The input as a reader, for solutions that read it a line at a time without holding all of
+    /// it.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::File(path) => crypt::open_input(path),
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// The command line options that choose which input to solve a day's puzzle with.
+///
+/// Each day's files are in a `dayNN` directory of the data directory, which is the first of
+///
+/// 1. the `--data-dir DIR` option,
+/// 2. the `AOC_DATA_DIR` environment variable,
+/// 3. the `data_dir` setting in the [Config] file,
+/// 4. the directory that holds the day crates.
+///
+/// The puzzle input is `input.txt` in the day's directory, unless `--input PATH` names another file,
+/// or `-` for stdin, or `--example NAME` picks one of the day's examples. Inputs can be kept
+/// encrypted, as `input.txt.enc`, which is read when there's no `input.txt` (see [crate::crypt]).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InputOptions {
+    pub data_dir: Option<PathBuf>,
+    pub input: Option<String>,
+    pub example: Option<String>,
+}
+
+impl InputOptions {
+    /// Take the `--data-dir`, `--input` and `--example` options out of the command line arguments,
+    /// leaving the rest of them.
+    pub fn take_from_args(args: &mut Vec<String>) -> Result<InputOptions, String> {
+        let options = InputOptions {
+            data_dir: take_option(args, "--data-dir")?.map(PathBuf::from),
+            input: take_option(args, "--input")?,
+            example: take_option(args, "--example")?,
+        };
+
+        if options.input.is_some() && options.example.is_some() {
+            return Err("Only one of --input and --example can be given".to_string());
+        }
+
+        Ok(options)
+    }
+
+    /// Take a data directory given before the day numbers, in the `aoc2022 <data-dir> <day>...`
+    /// calling style, out of the arguments, unless `--data-dir` was given. Only an existing
+    /// directory that isn't a number counts, so a day number is never taken for one.
+    pub fn take_positional_data_dir(&mut self, args: &mut Vec<String>) {
+        let Some(first) = args.first() else {
+            return;
+        };
+        if self.data_dir.is_none() && first.parse::<u32>().is_err() && Path::new(first).is_dir() {
+            self.data_dir = Some(PathBuf::from(args.remove(0)));
+        }
+    }
+
+    /// The directory holding each day's directory of inputs and answers.
+    pub fn data_dir(&self) -> Result<PathBuf, String> {
+        if let Some(data_dir) = &self.data_dir {
+            return Ok(data_dir.clone());
+        }
+
+        if let Some(data_dir) = env::var_os(DATA_DIR_VARIABLE) {
+            return Ok(PathBuf::from(data_dir));
+        }
+
+        if let Some(data_dir) = Config::load()?.get_path("data_dir") {
+            return Ok(data_dir);
+        }
+
+        Ok(default_data_dir())
+    }
+
+    /// The directory with a day's inputs and answers.
+    pub fn day_dir(&self, day: &Day) -> Result<PathBuf, String> {
+        Ok(self.data_dir()?.join(day.directory_name()))
+    }
+
+    /// Where to read the puzzle input for a day from.
+    pub fn source_for(&self, day: &Day) -> Result<InputSource, String> {
+        if let Some(input) = &self.input {
+            return Ok(match input.as_str() {
+                "-" => InputSource::Stdin,
+                path => InputSource::File(PathBuf::from(path)),
+            });
+        }
+
+        let day_dir = self.day_dir(day)?;
+
+        match &self.example {
+            Some(name) => find_example(&day_dir, name).map(InputSource::File),
+            None => Ok(InputSource::File(day_dir.join(INPUT_FILENAME))),
+        }
+    }
+}
+
+/// The directory the day crates are in, next to this crate.
+fn default_data_dir() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+/// Find the example called `name` in a day's directory. The name can be the whole file name, like
+/// `small-example.txt`, or just the part before `-example.txt`, like `small`.
+fn find_example(day_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let candidates = [
+        name.to_string(),
+        format!("{}.txt", name),
+        format!("{}-example.txt", name),
+    ];
+
+    candidates
+        .iter()
+        .map(|candidate| day_dir.join(candidate))
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            format!(
+                "There is no example called '{}' in {}",
+                name,
+                day_dir.display()
+            )
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InputOptions, InputSource};
+    use crate::{Day, Solution};
+    use std::fs;
+    use std::path::PathBuf;
+
+    struct Nothing;
+
+    impl Solution for Nothing {
+        type Input = ();
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn parse(_input: &str) -> Result<(), crate::ParseError> {
+            Ok(())
+        }
+        fn part1(_input: &()) -> u32 {
+            0
+        }
+        fn part2(_input: &()) -> u32 {
+            0
+        }
+    }
+
+    #[test]
+    fn inputs_are_found_in_the_day_directory() {
+        let data_dir = std::env::temp_dir().join(format!("aoc-input-test-{}", std::process::id()));
+        let day_dir = data_dir.join("day07");
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::write(day_dir.join("small-example.txt"), "").unwrap();
+        fs::write(day_dir.join("example.txt"), "").unwrap();
+
+        let day = Day::new::<Nothing>(7);
+        let options = |args: &[&str]| {
+            let mut args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            args.extend(["--data-dir".to_string(), data_dir.display().to_string()]);
+            InputOptions::take_from_args(&mut args).unwrap()
+        };
+
+        let source = |args: &[&str]| options(args).source_for(&day);
+
+        assert!(source(&[]) == Ok(InputSource::File(day_dir.join("input.txt"))));
+        assert!(source(&["--input", "-"]) == Ok(InputSource::Stdin));
+        assert!(source(&["--input", "a.txt"]) == Ok(InputSource::File(PathBuf::from("a.txt"))));
+        assert!(
+            source(&["--example", "small"])
+                == Ok(InputSource::File(day_dir.join("small-example.txt")))
+        );
+        assert!(
+            source(&["--example", "example.txt"])
+                == Ok(InputSource::File(day_dir.join("example.txt")))
+        );
+        assert!(source(&["--example", "large"]).is_err());
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+
+    #[test]
+    fn data_dir_can_come_before_the_days() {
+        let data_dir = std::env::temp_dir().display().to_string();
+        let mut args: Vec<String> = [data_dir.as_str(), "1", "2"].map(String::from).to_vec();
+        let mut options = InputOptions::take_from_args(&mut args).unwrap();
+        options.take_positional_data_dir(&mut args);
+        assert!(options.data_dir == Some(PathBuf::from(&data_dir)));
+        assert!(args == ["1", "2"]);
+
+        // Day numbers and anything that isn't a directory are left alone.
+        options.take_positional_data_dir(&mut args);
+        assert!(args == ["1", "2"]);
+        let mut args: Vec<String> = vec!["no-such-directory".to_string()];
+        let mut options = InputOptions::default();
+        options.take_positional_data_dir(&mut args);
+        assert!(options.data_dir.is_none() && args.len() == 1);
+    }
+
+    #[test]
+    fn input_and_example_are_exclusive() {
+        let mut args: Vec<String> = ["--input", "-", "--example", "small"]
+            .map(String::from)
+            .to_vec();
+        assert!(InputOptions::take_from_args(&mut args).is_err());
+    }
+}
@@ -1,8 +1,117 @@
-use std::{env, fs};
+mod answer;
+mod args;
+mod config;
+pub mod crypt;
+#[cfg(feature = "fetch")]
+mod fetch;
+mod generate;
+mod input;
+pub mod log;
+pub mod memory;
+mod output;
+mod parse_error;
+mod registry;
+mod scan;
+mod sections;
 
-pub fn read_input_file_to_string() -> String {
-    let args: Vec<String> = env::args().collect();
-    let filename = args.get(1).expect("Missing filename argument");
-    fs::read_to_string(&filename).expect("Unable to read file")
+pub use answer::Answer;
+pub use args::{take_flag, take_option};
+pub use config::{Config, CONFIG_VARIABLE};
+#[cfg(feature = "fetch")]
+pub use fetch::{FetchError, Fetched, Fetcher};
+pub use generate::{Generated, Generator, Rng};
+pub use input::{InputOptions, InputSource, DATA_DIR_VARIABLE, INPUT_FILENAME};
+pub use memory::MemoryUsage;
+pub use output::{json_string, print_answer, run_to_json, Format};
+pub use parse_error::{parse_lines, parse_number, read_lines, ParseError};
+pub use registry::{Alternative, Day, Part, Registry, Run, MAIN_IMPLEMENTATION};
+pub use scan::{scan_line, FromCaptures, ScanValue};
+pub use sections::{sections, Section, Sections};
+
+use std::io::BufRead;
+use std::{env, process};
+
+/// A solution to one day's puzzle. Parsing the input is separate from solving either part so the
+/// parts can be called (and timed) on their own.
+pub trait Solution {
+    type Input;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Parse the input from a reader. Solutions that can read it a line at a time do, so an input
+    /// of any size is parsed in the same memory, and their `parse` reads from the string's bytes.
+    /// Others read it all and [Solution::parse] it.
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, ParseError> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|e| ParseError::new(format!("Unable to read the input: {}", e)))?;
+        Self::parse(&input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Other ways of solving the parts, like a simpler or a faster one, which should give the same
+    /// answers as `part1` and `part2`. The runner's `compare` command runs them side by side.
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        Vec::new()
+    }
+
+    /// Ways of making up random inputs of different shapes, the usual shape first. The runner's
+    /// `gen` command prints them.
+    fn generators() -> Vec<Generator> {
+        Vec::new()
+    }
 }
 
+/// Solve both parts of day `day_number`'s puzzle.
+///
+/// The input is found as described in [InputOptions], and a file named on its own is the same as
+/// `--input`. A `--format json` option prints the answers as JSON, with their timings, and `-v`
+/// or `-vv` turn on the solution's [log] messages.
+pub fn main<S: Solution>(day_number: u32) {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let (format, mut options) = Format::take_from_args(&mut args)
+        .and_then(|format| Ok((format, InputOptions::take_from_args(&mut args)?)))
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    let level = log::Level::take_from_args(&mut args)
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    log::set_level(level);
+
+    match args.as_slice() {
+        [] => (),
+        [filename] if options.input.is_none() && options.example.is_none() => {
+            options.input = Some(filename.clone());
+        }
+        [.., extra] => exit_with_usage_error(&format!("Unexpected argument '{}'", extra)),
+    }
+
+    let day = Day::new::<S>(day_number);
+    let source = options
+        .source_for(&day)
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    let mut reader = source.open().unwrap_or_else(|e| {
+        eprintln!("Unable to read {}: {}", source, e);
+        process::exit(1);
+    });
+
+    match day.run_reader(&mut reader, Part::all()) {
+        Ok(runs) => {
+            for (part, run) in Part::all().iter().zip(&runs) {
+                format.print_run(day_number, *part, run);
+            }
+        }
+        Err(error) => {
+            eprintln!("{}", error.with_file(source.to_string()));
+            process::exit(1);
+        }
+    }
+}
+
+fn exit_with_usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
@@ -0,0 +1,130 @@
+use crate::args::take_flag;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The environment variable that sets the log level, when there's no `-v` or `-vv` flag.
+pub const LOG_VARIABLE: &str = "AOC_LOG";
+
+/// How much the solutions say about what they're doing, on stderr.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// Nothing but the answers.
+    #[default]
+    Off,
+    /// An overview of each step, like the state after each round. `-v` turns this on.
+    Debug,
+    /// Every detail, like each item each monkey throws. `-vv` turns this on.
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Level::Off),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!(
+                "Unknown log level '{}', expected 'off', 'debug' or 'trace'",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Off => write!(f, "off"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+impl Level {
+    /// Take the `-v` and `-vv` flags out of the command line arguments, leaving the rest of them.
+    /// Without them, the level comes from the `AOC_LOG` environment variable, and is off if that
+    /// isn't set either.
+    pub fn take_from_args(args: &mut Vec<String>) -> Result<Level, String> {
+        if take_flag(args, "-vv") {
+            take_flag(args, "-v");
+            return Ok(Level::Trace);
+        }
+        if take_flag(args, "-v") {
+            return Ok(Level::Debug);
+        }
+
+        match env::var(LOG_VARIABLE) {
+            Ok(level) if !level.is_empty() => level
+                .parse()
+                .map_err(|e| format!("{}: {}", LOG_VARIABLE, e)),
+            _ => Ok(Level::Off),
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+/// Set the log level for every thread.
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Off,
+        1 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+/// Whether messages at `level` are being written. `debug!` and `trace!` check this before their
+/// arguments are evaluated, so a message that's expensive to build, like a picture of a grid,
+/// doesn't need checking for separately.
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+/// Write a message to stderr, like `eprintln!`, if the log level is `debug` or above.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Write a message to stderr, like `eprintln!`, if the log level is `trace`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::Level;
+
+    #[test]
+    fn levels_are_taken_from_the_arguments() {
+        let mut args: Vec<String> = ["-v", "1", "-vv"].map(String::from).to_vec();
+        assert!(Level::take_from_args(&mut args) == Ok(Level::Trace));
+        assert!(args == vec!["1"]);
+
+        let mut args = vec!["-v".to_string()];
+        assert!(Level::take_from_args(&mut args) == Ok(Level::Debug));
+        assert!(args.is_empty());
+
+        assert!("trace".parse::<Level>() == Ok(Level::Trace));
+        assert!("loud".parse::<Level>().is_err());
+        assert!(Level::Debug < Level::Trace);
+    }
+}
@@ -0,0 +1,199 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+/// The global allocator, when allocations are being counted. Tests always count them, so that
+/// counting can be tested.
+#[cfg(any(test, feature = "count-allocations"))]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The memory used by one phase of a solution.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryUsage {
+    /// How many times memory was allocated or reallocated.
+    pub allocations: u64,
+    /// The total size of every allocation, including memory that was freed again.
+    pub bytes: u64,
+    /// The most memory the phase had allocated at once, not counting anything allocated before it
+    /// started.
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// A number of bytes in whichever of bytes, KiB or MiB reads best.
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * KIB;
+
+    if bytes < KIB {
+        format!("{} B", bytes)
+    } else if bytes < MIB {
+        format!("{:.1} KiB", bytes as f64 / KIB as f64)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    }
+}
+
+/// Whether allocations are being counted, which needs the `count-allocations` feature of this
+/// crate. Without it, the system allocator is used as usual and [measure] can't say anything.
+pub fn is_counting() -> bool {
+    cfg!(any(test, feature = "count-allocations"))
+}
+
+/// Call `f`, and measure the memory it allocates on this thread, if allocations are being counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    if !is_counting() {
+        return (f(), None);
+    }
+
+    let before = COUNTS.with(|counts| {
+        let mut before = counts.get();
+        before.peak = before.live;
+        counts.set(before);
+        before
+    });
+
+    let result = f();
+
+    let after = COUNTS.with(Cell::get);
+    let usage = MemoryUsage {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak_bytes: (after.peak - before.live) as u64,
+    };
+
+    (result, Some(usage))
+}
+
+/// The running totals for a thread.
+#[derive(Clone, Copy)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    /// Memory can be freed by a different thread from the one that allocated it, so this can go
+    /// below zero.
+    live: i64,
+    peak: i64,
+}
+
+impl Counts {
+    fn allocate(&mut self, size: usize) {
+        self.allocations += 1;
+        self.bytes += size as u64;
+        self.live += size as i64;
+        self.peak = self.peak.max(self.live);
+    }
+
+    fn free(&mut self, size: usize) {
+        self.live -= size as i64;
+    }
+}
+
+thread_local! {
+    // Counting per thread keeps each measurement to the code being measured, even while other
+    // threads are busy. A constant initializer means using this never allocates itself.
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+/// Update this thread's counts, unless the thread is being torn down.
+fn count(update: impl FnOnce(&mut Counts)) {
+    let _ = COUNTS.try_with(|counts| {
+        let mut current = counts.get();
+        update(&mut current);
+        counts.set(current);
+    });
+}
+
+/// The system allocator, counting what's allocated on each thread as it goes.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            count(|counts| counts.allocate(layout.size()));
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            count(|counts| counts.allocate(layout.size()));
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        count(|counts| counts.free(layout.size()));
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            count(|counts| {
+                counts.free(layout.size());
+                counts.allocate(new_size);
+            });
+        }
+        new_pointer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_bytes, measure};
+    use std::hint::black_box;
+
+    #[test]
+    fn allocations_are_counted() {
+        let (_, usage) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(100);
+            v.push(1);
+            black_box(&v);
+            drop(v);
+            let b = Box::new([0u8; 200]);
+            black_box(&b);
+        });
+
+        let usage = usage.unwrap();
+        assert!(usage.allocations == 2);
+        assert!(usage.bytes == 1000);
+        // The vector was freed before the box was allocated.
+        assert!(usage.peak_bytes == 800);
+
+        let (kept, usage) = measure(|| vec![0u8; 64]);
+        assert!(usage.unwrap().peak_bytes == 64);
+
+        // Freeing memory allocated earlier doesn't count against the peak.
+        let (_, usage) = measure(|| drop(kept));
+        assert!(usage.unwrap() == Default::default());
+    }
+
+    #[test]
+    fn bytes_are_formatted_in_sensible_units() {
+        assert!(format_bytes(512) == "512 B");
+        assert!(format_bytes(1536) == "1.5 KiB");
+        assert!(format_bytes(3 * 1024 * 1024) == "3.0 MiB");
+    }
+}
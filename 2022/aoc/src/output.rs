@@ -0,0 +1,183 @@
+use crate::args::take_option;
+use crate::{Answer, MemoryUsage, Part, Run};
+use std::str::FromStr;
+
+/// How the answers are printed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// `Part 1: 70374`, for people to read.
+    #[default]
+    Text,
+    /// A JSON object on a line of its own for each part, for scripts to read.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format '{}', expected 'text' or 'json'", s)),
+        }
+    }
+}
+
+impl Format {
+    /// Take a `--format NAME` or `--format=NAME` option out of the command line arguments, leaving
+    /// the rest of them. The format is text if there's no option.
+    pub fn take_from_args(args: &mut Vec<String>) -> Result<Format, String> {
+        match take_option(args, "--format")? {
+            Some(name) => name.parse(),
+            None => Ok(Format::Text),
+        }
+    }
+
+    /// Print the answer to a part of a day's puzzle. Only JSON includes the timings, but both
+    /// include the memory used when allocations are being counted.
+    pub fn print_run(self, day: u32, part: Part, run: &Run) {
+        match self {
+            Format::Text => {
+                print_answer(part, &run.answer);
+                if let Some(memory) = run.parse_memory {
+                    println!("  Parse memory: {}", memory);
+                }
+                if let Some(memory) = run.solve_memory {
+                    println!("  Solve memory: {}", memory);
+                }
+            }
+            Format::Json => println!("{}", run_to_json(day, part, run)),
+        }
+    }
+}
+
+/// Print the answer to a part of the puzzle. Answers that span several lines start on their own
+/// line so they line up.
+pub fn print_answer(part: Part, answer: &Answer) {
+    if answer.is_multiline() {
+        println!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+/// A JSON object with the answer to a part of a day's puzzle and how long it took, like
+///
+/// ```text
+/// {"day":1,"part":1,"answer":70374,"parse_time_ns":41250,"solve_time_ns":291}
+/// ```
+///
+/// Integer answers are numbers, a missing answer is `null`, and every other answer is a string.
+/// The rows of glyphs are joined with newlines. When allocations are being counted, there are also
+/// `parse_memory` and `solve_memory` objects with `allocations`, `bytes` and `peak_bytes`.
+pub fn run_to_json(day: u32, part: Part, run: &Run) -> String {
+    let answer = match &run.answer {
+        Answer::Integer(value) => value.to_string(),
+        Answer::None => "null".to_string(),
+        answer => json_string(&answer.to_string()),
+    };
+
+    let mut memory = String::new();
+    for (name, usage) in [
+        ("parse_memory", run.parse_memory),
+        ("solve_memory", run.solve_memory),
+    ] {
+        if let Some(usage) = usage {
+            memory.push_str(&format!(",\"{}\":{}", name, memory_to_json(&usage)));
+        }
+    }
+
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_time_ns\":{},\"solve_time_ns\":{}{}}}",
+        day,
+        part,
+        answer,
+        run.parse_time.as_nanos(),
+        run.solve_time.as_nanos(),
+        memory
+    )
+}
+
+fn memory_to_json(usage: &MemoryUsage) -> String {
+    format!(
+        "{{\"allocations\":{},\"bytes\":{},\"peak_bytes\":{}}}",
+        usage.allocations, usage.bytes, usage.peak_bytes
+    )
+}
+
+/// Quote a string for JSON.
+pub fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run_to_json, Format};
+    use crate::{Answer, MemoryUsage, Part, Run};
+    use std::time::Duration;
+
+    fn run(answer: Answer) -> Run {
+        Run {
+            answer,
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_nanos(20),
+            parse_memory: None,
+            solve_memory: None,
+        }
+    }
+
+    #[test]
+    fn json_records_have_typed_answers() {
+        let json = run_to_json(1, Part::Two, &run(Answer::from(204610)));
+        assert!(
+            json == r#"{"day":1,"part":2,"answer":204610,"parse_time_ns":1500,"solve_time_ns":20}"#
+        );
+
+        let json = run_to_json(10, Part::Two, &run(Answer::glyphs("#.\n\"#")));
+        assert!(json.contains(r##""answer":"#.\n\"#""##));
+
+        let json = run_to_json(12, Part::One, &run(Answer::None));
+        assert!(json.contains(r#""answer":null"#));
+
+        let mut counted = run(Answer::from(1));
+        counted.solve_memory = Some(MemoryUsage {
+            allocations: 2,
+            bytes: 96,
+            peak_bytes: 64,
+        });
+        let json = run_to_json(3, Part::One, &counted);
+        assert!(json.ends_with(
+            r#""solve_time_ns":20,"solve_memory":{"allocations":2,"bytes":96,"peak_bytes":64}}"#
+        ));
+    }
+
+    #[test]
+    fn format_options_are_taken_from_the_arguments() {
+        let mut args: Vec<String> = ["input.txt", "--format", "json"].map(String::from).to_vec();
+        assert!(Format::take_from_args(&mut args) == Ok(Format::Json));
+        assert!(args == vec!["input.txt"]);
+
+        let mut args = vec!["--format=text".to_string()];
+        assert!(Format::take_from_args(&mut args) == Ok(Format::Text));
+        assert!(args.is_empty());
+
+        let mut args = vec!["--format".to_string()];
+        assert!(Format::take_from_args(&mut args).is_err());
+        assert!(Format::take_from_args(&mut vec!["--format=xml".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// An error found while parsing puzzle input, with enough context to point at where it went
+/// wrong.
+///
+/// Parsers that work on a single line, like `TryFrom<&str>` implementations, make errors with
+/// [ParseError::at] or [ParseError::at_token]. Whoever is reading the whole input fills in the line
+/// number and file name as the error makes its way back up.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    message: String,
+    file: Option<String>,
+    line_number: Option<usize>,
+    column: Option<usize>,
+    source_line: Option<String>,
+}
+
+impl ParseError {
+    /// An error that isn't about any particular place in the input.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            file: None,
+            line_number: None,
+            column: None,
+            source_line: None,
+        }
+    }
+
+    /// An error at a column of a line of input. Columns count from 1.
+    pub fn at(source_line: &str, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            column: Some(column),
+            source_line: Some(source_line.to_string()),
+            ..ParseError::new(message)
+        }
+    }
+
+    /// An error about a token in a line of input. `token` should be a slice of `source_line`, so
+    /// the error can point at it.
+    pub fn at_token(source_line: &str, token: &str, message: impl Into<String>) -> ParseError {
+        ParseError::at(source_line, column_of(source_line, token), message)
+    }
+
+    /// Point an error made without a location, like one from parsing a single token, at `token` in
+    /// `source_line`.
+    pub fn with_token(mut self, source_line: &str, token: &str) -> ParseError {
+        self.column = Some(column_of(source_line, token));
+        self.source_line = Some(source_line.to_string());
+        self
+    }
+
+    pub fn with_line_number(mut self, line_number: usize) -> ParseError {
+        self.line_number = Some(line_number);
+        self
+    }
+
+    pub fn with_file(mut self, file: impl Into<String>) -> ParseError {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    pub fn line_number(&self) -> Option<usize> {
+        self.line_number
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    pub fn source_line(&self) -> Option<&str> {
+        self.source_line.as_deref()
+    }
+}
+
+/// The column, counting from 1, where `token` starts in `line`. Tokens that aren't slices of the
+/// line are placed at the start of it.
+fn column_of(line: &str, token: &str) -> usize {
+    let line_start = line.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+    if token_start >= line_start && token_start <= line_start + line.len() {
+        line[..token_start - line_start].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+/// Parse `token`, a slice of `line`, as a number.
+pub fn parse_number<T: FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    token.parse::<T>().map_err(|_| {
+        ParseError::at_token(line, token, format!("Expected a number, found '{}'", token))
+    })
+}
+
+/// Parse each line of the input with `parse_line`, filling in the line number of any error.
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.with_line_number(i + 1)))
+        .collect()
+}
+
+/// Parse each line read from `reader` with `parse_line`, like [parse_lines], but a line at a time
+/// into the same buffer, so an input of any size is read in the memory its longest line needs.
+pub fn read_lines<R, F>(mut reader: R, mut parse_line: F) -> Result<(), ParseError>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<(), ParseError>,
+{
+    let mut line = String::new();
+    for line_number in 1.. {
+        line.clear();
+        let length = reader.read_line(&mut line).map_err(|e| {
+            ParseError::new(format!("Unable to read the input: {}", e))
+                .with_line_number(line_number)
+        })?;
+        if length == 0 {
+            break;
+        }
+
+        let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+        let trimmed = trimmed.strip_suffix('\r').unwrap_or(trimmed);
+        parse_line(trimmed).map_err(|e| e.with_line_number(line_number))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+
+        let location = match (&self.file, self.line_number, self.column) {
+            (Some(file), Some(line), Some(column)) => Some(format!("{}:{}:{}", file, line, column)),
+            (Some(file), Some(line), None) => Some(format!("{}:{}", file, line)),
+            (Some(file), None, _) => Some(file.clone()),
+            (None, Some(line), Some(column)) => Some(format!("line {}, column {}", line, column)),
+            (None, Some(line), None) => Some(format!("line {}", line)),
+            (None, None, _) => None,
+        };
+        if let Some(location) = location {
+            write!(f, "\n --> {}", location)?;
+        }
+
+        if let (Some(source_line), Some(column)) = (&self.source_line, self.column) {
+            let gutter = self.line_number.map(|n| n.to_string()).unwrap_or_default();
+            let blank_gutter = " ".repeat(gutter.len());
+            write!(f, "\n{} |", blank_gutter)?;
+            write!(f, "\n{} | {}", gutter, source_line)?;
+            write!(f, "\n{} | {}^", blank_gutter, " ".repeat(column - 1))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_lines, parse_number, read_lines, ParseError};
+
+    #[test]
+    fn tokens_are_located_in_their_line() {
+        let line = "move 1 from x to 3";
+        let token = &line[12..13];
+        let error = ParseError::at_token(line, token, "Expected a number");
+        assert!(error.column() == Some(13));
+    }
+
+    #[test]
+    fn display_points_at_the_column() {
+        let line = "move 1 from x to 3";
+        let error = parse_number::<usize>(line, &line[12..13])
+            .unwrap_err()
+            .with_line_number(12)
+            .with_file("input.txt");
+
+        let expected = "\
+error: Expected a number, found 'x'
+ --> input.txt:12:13
+   |
+12 | move 1 from x to 3
+   |             ^";
+        assert!(error.to_string() == expected);
+    }
+
+    #[test]
+    fn parse_lines_numbers_lines_from_one() {
+        let input = "1\n2\nthree\n4";
+        let error = parse_lines(input, |line| parse_number::<u32>(line, line)).unwrap_err();
+        assert!(error.line_number() == Some(3));
+        assert!(error.column() == Some(1));
+
+        let numbers = parse_lines("1\n2", |line| parse_number::<u32>(line, line)).unwrap();
+        assert!(numbers == vec![1, 2]);
+    }
+
+    #[test]
+    fn read_lines_reads_like_parse_lines() {
+        let mut sum = 0;
+        read_lines("1\r\n2\n\n3".as_bytes(), |line| {
+            if !line.is_empty() {
+                sum += parse_number::<u32>(line, line)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert!(sum == 6);
+
+        let error = read_lines("1\n2\nthree\n4".as_bytes(), |line| {
+            parse_number::<u32>(line, line).map(|_| ())
+        })
+        .unwrap_err();
+        assert!(error.line_number() == Some(3));
+
+        let error = read_lines(&[b'1', b'\n', 0xff][..], |_| Ok(())).unwrap_err();
+        assert!(error.line_number() == Some(2));
+    }
+}
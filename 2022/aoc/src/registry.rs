@@ -0,0 +1,302 @@
+use crate::memory::{self, MemoryUsage};
+use crate::{Answer, Generator, ParseError, Solution};
+use std::fmt;
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn all() -> &'static [Part] {
+        &[Part::One, Part::Two]
+    }
+
+    pub fn from_number(number: u32) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle, and how long it took to find it.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// The memory used by each phase, if allocations are being counted.
+    pub parse_memory: Option<MemoryUsage>,
+    pub solve_memory: Option<MemoryUsage>,
+}
+
+impl Run {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+}
+
+/// What `part1` and `part2` of a [Solution] are called, beside its [Alternative]s.
+pub const MAIN_IMPLEMENTATION: &str = "main";
+
+/// Another implementation of one part of a [Solution], with a name to tell it apart.
+pub struct Alternative<I> {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&I) -> Answer,
+}
+
+impl<I> Alternative<I> {
+    pub fn new(name: &'static str, part: Part, solve: fn(&I) -> Answer) -> Alternative<I> {
+        Alternative { name, part, solve }
+    }
+}
+
+/// The runs of every implementation of a part, by name.
+type Implementations = Vec<(&'static str, Run)>;
+
+/// Parses an input as it's read, and solves some of the parts with it.
+type RunReader = fn(&mut dyn BufRead, &[Part]) -> Result<Vec<Run>, ParseError>;
+
+/// A day's solution with its types erased, so solutions for every day can be kept together in a
+/// [Registry].
+#[derive(Clone, Copy)]
+pub struct Day {
+    number: u32,
+    run: fn(&str, Part) -> Result<Run, ParseError>,
+    run_reader: RunReader,
+    run_implementations: fn(&str, Part) -> Result<Implementations, ParseError>,
+    generators: fn() -> Vec<Generator>,
+}
+
+impl Day {
+    pub fn new<S: Solution>(number: u32) -> Day {
+        Day {
+            number,
+            run: run::<S>,
+            run_reader: run_reader::<S>,
+            run_implementations: run_implementations::<S>,
+            generators: S::generators,
+        }
+    }
+
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// The name of the directory holding this day's crate and input files.
+    pub fn directory_name(&self) -> String {
+        format!("day{:02}", self.number)
+    }
+
+    /// Parse the input and solve one part of the puzzle with it, timing each step.
+    pub fn run(&self, input: &str, part: Part) -> Result<Run, ParseError> {
+        (self.run)(input, part)
+    }
+
+    /// Parse the input as it's read from `reader`, and solve each of `parts` with it, timing each
+    /// step. Every run has the same parse time, since parsing happens once.
+    pub fn run_reader(
+        &self,
+        reader: &mut dyn BufRead,
+        parts: &[Part],
+    ) -> Result<Vec<Run>, ParseError> {
+        (self.run_reader)(reader, parts)
+    }
+
+    /// Parse the input once and solve one part with every implementation of it, the main one
+    /// first and then its [Alternative]s, timing each.
+    pub fn run_implementations(
+        &self,
+        input: &str,
+        part: Part,
+    ) -> Result<Implementations, ParseError> {
+        (self.run_implementations)(input, part)
+    }
+
+    /// The ways of making up inputs for this day, the usual shape first.
+    pub fn generators(&self) -> Vec<Generator> {
+        (self.generators)()
+    }
+}
+
+/// The parsed input, with how long parsing took and the memory it used.
+fn parse<S: Solution>(
+    input: &str,
+) -> Result<(S::Input, Duration, Option<MemoryUsage>), ParseError> {
+    measure_parse(|| S::parse(input))
+}
+
+fn measure_parse<I>(
+    parse: impl FnOnce() -> Result<I, ParseError>,
+) -> Result<(I, Duration, Option<MemoryUsage>), ParseError> {
+    let parse_start = Instant::now();
+    let (input, parse_memory) = memory::measure(parse);
+    let input = input?;
+    Ok((input, parse_start.elapsed(), parse_memory))
+}
+
+fn solve<I>(
+    input: &I,
+    solve: impl FnOnce(&I) -> Answer,
+    parse_time: Duration,
+    parse_memory: Option<MemoryUsage>,
+) -> Run {
+    let solve_start = Instant::now();
+    let (answer, solve_memory) = memory::measure(|| solve(input));
+    let solve_time = solve_start.elapsed();
+
+    Run {
+        answer,
+        parse_time,
+        solve_time,
+        parse_memory,
+        solve_memory,
+    }
+}
+
+/// The main implementation of a part.
+fn main_implementation<S: Solution>(part: Part) -> fn(&S::Input) -> Answer {
+    match part {
+        Part::One => |input| S::part1(input).into(),
+        Part::Two => |input| S::part2(input).into(),
+    }
+}
+
+fn run<S: Solution>(input: &str, part: Part) -> Result<Run, ParseError> {
+    let (input, parse_time, parse_memory) = parse::<S>(input)?;
+    Ok(solve(
+        &input,
+        main_implementation::<S>(part),
+        parse_time,
+        parse_memory,
+    ))
+}
+
+fn run_reader<S: Solution>(
+    reader: &mut dyn BufRead,
+    parts: &[Part],
+) -> Result<Vec<Run>, ParseError> {
+    let (input, parse_time, parse_memory) = measure_parse(|| S::parse_reader(reader))?;
+    Ok(parts
+        .iter()
+        .map(|part| {
+            solve(
+                &input,
+                main_implementation::<S>(*part),
+                parse_time,
+                parse_memory,
+            )
+        })
+        .collect())
+}
+
+fn run_implementations<S: Solution>(
+    input: &str,
+    part: Part,
+) -> Result<Implementations, ParseError> {
+    let (input, parse_time, parse_memory) = parse::<S>(input)?;
+
+    let alternatives = S::alternatives()
+        .into_iter()
+        .filter(|alternative| alternative.part == part)
+        .map(|alternative| (alternative.name, alternative.solve));
+    Ok(
+        std::iter::once((MAIN_IMPLEMENTATION, main_implementation::<S>(part)))
+            .chain(alternatives)
+            .map(|(name, implementation)| {
+                (
+                    name,
+                    solve(&input, implementation, parse_time, parse_memory),
+                )
+            })
+            .collect(),
+    )
+}
+
+#[derive(Default)]
+pub struct Registry {
+    days: Vec<Day>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry { days: Vec::new() }
+    }
+
+    pub fn register<S: Solution>(mut self, number: u32) -> Registry {
+        assert!(
+            self.get(number).is_none(),
+            "Day {number} is already registered"
+        );
+        self.days.push(Day::new::<S>(number));
+        self.days.sort_by_key(|d| d.number);
+        self
+    }
+
+    pub fn get(&self, number: u32) -> Option<&Day> {
+        self.days.iter().find(|d| d.number == number)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Day> {
+        self.days.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Day, Part};
+    use crate::{Answer, ParseError, Solution};
+
+    /// Counts the lines, and the lines that are longer than the first.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = Vec<usize>;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.lines().map(str::len).collect())
+        }
+
+        fn part1(lengths: &Self::Input) -> usize {
+            lengths.len()
+        }
+
+        fn part2(lengths: &Self::Input) -> usize {
+            lengths
+                .iter()
+                .filter(|&&length| length > lengths[0])
+                .count()
+        }
+    }
+
+    #[test]
+    fn readers_are_parsed_once_for_every_part() {
+        let day = Day::new::<Lines>(1);
+        let runs = day
+            .run_reader(&mut "ab\nc\ndef\n".as_bytes(), Part::all())
+            .unwrap();
+        let answers: Vec<&Answer> = runs.iter().map(|run| &run.answer).collect();
+        assert!(answers == [&Answer::from(3), &Answer::from(1)]);
+        assert!(runs[0].parse_time == runs[1].parse_time);
+
+        let runs = day.run_reader(&mut "a\n".as_bytes(), &[Part::Two]).unwrap();
+        assert!(runs.len() == 1 && runs[0].answer == Answer::from(0));
+    }
+}
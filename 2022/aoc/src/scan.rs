@@ -0,0 +1,265 @@
+use crate::{parse_number, ParseError};
+
+/// Match a line against a pattern like `"move {} from {} to {}"`, and parse the text in place of
+/// each `{}` into a value.
+///
+/// The values are read into a tuple with one element per `{}`, or a single value if there is only
+/// one. Any type that implements [ScanValue] can be used, including `&str` for text that needs
+/// more work than a single parse. The type of the result is usually inferred, but can be given
+/// after the pattern.
+///
+/// ```
+/// let (quantity, from, to): (usize, usize, usize) =
+///     aoc::scan!("move 3 from 1 to 2", "move {} from {} to {}").unwrap();
+/// assert!((quantity, from, to) == (3, 1, 2));
+///
+/// let monkey = aoc::scan!("Monkey 7:", "Monkey {}:", usize).unwrap();
+/// assert!(monkey == 7);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:expr $(,)?) => {
+        $crate::scan_line($line, $pattern)
+    };
+    ($line:expr, $pattern:expr, $t:ty $(,)?) => {
+        $crate::scan_line::<$t>($line, $pattern)
+    };
+    ($line:expr, $pattern:expr, $($t:ty),+ $(,)?) => {
+        $crate::scan_line::<($($t,)+)>($line, $pattern)
+    };
+}
+
+/// A value that can be read from the text matched by a `{}` in a [scan!] pattern.
+pub trait ScanValue<'a>: Sized {
+    /// Parse `token`, a slice of `line`.
+    fn scan(line: &'a str, token: &'a str) -> Result<Self, ParseError>;
+}
+
+impl<'a> ScanValue<'a> for &'a str {
+    fn scan(_line: &'a str, token: &'a str) -> Result<Self, ParseError> {
+        Ok(token)
+    }
+}
+
+impl<'a> ScanValue<'a> for String {
+    fn scan(_line: &'a str, token: &'a str) -> Result<Self, ParseError> {
+        Ok(token.to_string())
+    }
+}
+
+impl<'a> ScanValue<'a> for char {
+    fn scan(line: &'a str, token: &'a str) -> Result<Self, ParseError> {
+        let mut chars = token.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(ParseError::at_token(
+                line,
+                token,
+                format!("Expected a single character, found '{}'", token),
+            )),
+        }
+    }
+}
+
+macro_rules! scan_value_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> ScanValue<'a> for $t {
+                fn scan(line: &'a str, token: &'a str) -> Result<Self, ParseError> {
+                    parse_number(line, token)
+                }
+            }
+        )*
+    };
+}
+
+scan_value_from_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// The values read from every `{}` in a [scan!] pattern.
+pub trait FromCaptures<'a>: Sized {
+    /// How many `{}`s the pattern needs to have.
+    const LEN: usize;
+
+    /// Parse the captured tokens, which are slices of `line`. There are always `LEN` of them.
+    fn from_captures(line: &'a str, captures: &[&'a str]) -> Result<Self, ParseError>;
+}
+
+impl<'a, T: ScanValue<'a>> FromCaptures<'a> for T {
+    const LEN: usize = 1;
+
+    fn from_captures(line: &'a str, captures: &[&'a str]) -> Result<Self, ParseError> {
+        T::scan(line, captures[0])
+    }
+}
+
+macro_rules! from_captures_for_tuple {
+    ($len:expr; $($t:ident $i:tt),+) => {
+        impl<'a, $($t: ScanValue<'a>),+> FromCaptures<'a> for ($($t,)+) {
+            const LEN: usize = $len;
+
+            fn from_captures(line: &'a str, captures: &[&'a str]) -> Result<Self, ParseError> {
+                Ok(($($t::scan(line, captures[$i])?,)+))
+            }
+        }
+    };
+}
+
+from_captures_for_tuple!(1; A 0);
+from_captures_for_tuple!(2; A 0, B 1);
+from_captures_for_tuple!(3; A 0, B 1, C 2);
+from_captures_for_tuple!(4; A 0, B 1, C 2, D 3);
+from_captures_for_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+from_captures_for_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// The error for a line that doesn't have `literal` at `position`.
+fn expected_literal(line: &str, position: usize, literal: &str) -> ParseError {
+    let matching_length: usize = line[position..]
+        .chars()
+        .zip(literal.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    let mismatch = position + matching_length;
+    let expected = literal.trim();
+
+    if line[mismatch..].trim().is_empty() {
+        return ParseError::at(line, mismatch + 1, format!("Missing '{}'", expected));
+    }
+
+    // Point at the whole word the mismatch is in, rather than at the first character that differs.
+    let word_start = line[..mismatch]
+        .rfind(char::is_whitespace)
+        .map_or(0, |i| i + 1)
+        .max(position);
+    let found = line[word_start..].split_whitespace().next().unwrap();
+
+    ParseError::at_token(
+        line,
+        found,
+        format!("Expected '{}', found '{}'", expected, found),
+    )
+}
+
+/// Match `line` against `pattern`, returning the text in place of each `{}`.
+///
+/// Each `{}` matches as little text as it can, up to the first place the rest of the pattern
+/// carries on from, except that a `{}` at the end of the pattern matches the rest of the line.
+/// Since `{}`s are always separated by some text, that's the only time a piece of the pattern
+/// between them is empty.
+fn captures<'a>(line: &'a str, pattern: &str) -> Result<Vec<&'a str>, ParseError> {
+    let mut literals = pattern.split("{}");
+
+    // There is always at least one piece, even for an empty pattern.
+    let first_literal = literals.next().unwrap();
+    if !line.starts_with(first_literal) {
+        return Err(expected_literal(line, 0, first_literal));
+    }
+    let mut position = first_literal.len();
+
+    let mut captures = Vec::new();
+    for literal in literals {
+        let rest = &line[position..];
+
+        let length = if literal.is_empty() {
+            rest.len()
+        } else {
+            match rest.find(literal) {
+                Some(length) => length,
+                None => {
+                    let token_length = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                    return Err(expected_literal(line, position + token_length, literal));
+                }
+            }
+        };
+
+        let capture = &rest[..length];
+        if capture.trim().is_empty() {
+            return Err(ParseError::at_token(line, capture, "Missing a value"));
+        }
+
+        captures.push(capture);
+        position += length + literal.len();
+    }
+
+    if position != line.len() {
+        let extra = line[position..].trim_start();
+        return Err(ParseError::at_token(
+            line,
+            extra,
+            "Unexpected text at the end of the line",
+        ));
+    }
+
+    Ok(captures)
+}
+
+/// Match `line` against `pattern` and parse the values in place of each `{}`. This is what
+/// [scan!] expands to.
+///
+/// # Panics
+///
+/// If the number of `{}`s in `pattern` doesn't match the number of values asked for, or two `{}`s
+/// are next to each other, since those are mistakes in the code rather than in the input.
+pub fn scan_line<'a, T: FromCaptures<'a>>(line: &'a str, pattern: &str) -> Result<T, ParseError> {
+    assert!(
+        !pattern.contains("{}{}"),
+        "The pattern {:?} has two {{}}s next to each other",
+        pattern
+    );
+    let placeholders = pattern.matches("{}").count();
+    assert!(
+        placeholders == T::LEN,
+        "The pattern {:?} has {} {{}}s, but {} values were asked for",
+        pattern,
+        placeholders,
+        T::LEN
+    );
+
+    T::from_captures(line, &captures(line, pattern)?)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn values_are_parsed_into_their_types() {
+        let (quantity, from, to): (usize, u8, i64) =
+            scan!("move 13 from 1 to 2", "move {} from {} to {}").unwrap();
+        assert!((quantity, from, to) == (13, 1, 2));
+
+        let (term, operator, other): (&str, char, String) =
+            scan!("  Operation: new = old * 19", "  Operation: new = {} {} {}").unwrap();
+        assert!(term == "old" && operator == '*' && other == "19");
+
+        assert!(scan!("Monkey 3:", "Monkey {}:", usize).unwrap() == 3);
+    }
+
+    #[test]
+    fn the_last_value_takes_the_rest_of_the_line() {
+        let items = scan!("Starting items: 79, 98", "Starting items: {}", &str).unwrap();
+        assert!(items == "79, 98");
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let error = scan!("move 1 frm 2 to 3", "move {} from {} to {}", u32, u32, u32).unwrap_err();
+        assert!(error.message() == "Expected 'from', found 'frm'");
+        assert!(error.column() == Some(8));
+
+        let error =
+            scan!("move x from 2 to 3", "move {} from {} to {}", u32, u32, u32).unwrap_err();
+        assert!(error.message() == "Expected a number, found 'x'");
+        assert!(error.column() == Some(6));
+
+        let error = scan!("move 1 from 2 to", "move {} from {} to {}", u32, u32, u32).unwrap_err();
+        assert!(error.message() == "Missing 'to'");
+
+        let error = scan!("Monkey 3: x", "Monkey {}:", u32).unwrap_err();
+        assert!(error.column() == Some(11));
+    }
+
+    #[test]
+    #[should_panic]
+    fn patterns_must_match_the_number_of_values() {
+        let _ = scan!("move 1 from 2 to 3", "move {} from {} to {}", u32, u32);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::ParseError;
+use std::iter::Enumerate;
+use std::str::Lines;
+
+/// A run of consecutive non-blank lines of input, like one elf's snacks on day 1 or one monkey's
+/// description on day 11.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Section<'a> {
+    first_line_number: usize,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    /// The line number of the first line of the section in the whole input. Line numbers count
+    /// from 1.
+    pub fn first_line_number(&self) -> usize {
+        self.first_line_number
+    }
+
+    pub fn lines(&self) -> &[&'a str] {
+        &self.lines
+    }
+
+    /// The lines of the section along with their line numbers in the whole input.
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        (self.first_line_number..).zip(self.lines.iter().copied())
+    }
+
+    /// Parse each line of the section with `parse_line`, filling in the line number of any error.
+    pub fn parse_lines<T, F>(&self, mut parse_line: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&str) -> Result<T, ParseError>,
+    {
+        self.numbered_lines()
+            .map(|(line_number, line)| {
+                parse_line(line).map_err(|e| e.with_line_number(line_number))
+            })
+            .collect()
+    }
+}
+
+/// An iterator over the blank-line-separated sections of some input. See [sections].
+pub struct Sections<'a> {
+    lines: Enumerate<Lines<'a>>,
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        let mut section: Option<Section<'a>> = None;
+
+        for (i, line) in self.lines.by_ref() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if line.trim().is_empty() {
+                if section.is_some() {
+                    break;
+                }
+                continue;
+            }
+
+            section
+                .get_or_insert_with(|| Section {
+                    first_line_number: i + 1,
+                    lines: Vec::new(),
+                })
+                .lines
+                .push(line);
+        }
+
+        section
+    }
+}
+
+/// Split the input into sections separated by blank lines.
+///
+/// The last section ends at the end of the input whether or not it is followed by a blank line.
+/// Lines may end with `\n` or `\r\n`, and lines holding only whitespace count as blank. Runs of
+/// several blank lines separate sections the same way as a single blank line, so sections are
+/// never empty.
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections {
+        lines: input.lines().enumerate(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::sections;
+
+    #[test]
+    fn last_section_needs_no_trailing_blank_line() {
+        let with_blank_line: Vec<Vec<&str>> = sections("1\n2\n\n3\n\n")
+            .map(|s| s.lines().to_vec())
+            .collect();
+        let without_blank_line: Vec<Vec<&str>> =
+            sections("1\n2\n\n3").map(|s| s.lines().to_vec()).collect();
+
+        assert!(with_blank_line == vec![vec!["1", "2"], vec!["3"]]);
+        assert!(without_blank_line == with_blank_line);
+    }
+
+    #[test]
+    fn crlf_line_endings_are_the_same_as_lf() {
+        let crlf: Vec<Vec<&str>> = sections("1\r\n2\r\n\r\n3\r")
+            .map(|s| s.lines().to_vec())
+            .collect();
+        assert!(crlf == vec![vec!["1", "2"], vec!["3"]]);
+    }
+
+    #[test]
+    fn sections_know_their_line_numbers() {
+        let input = "\n1\n2\n\n\n3\nx\n";
+        let all: Vec<_> = sections(input).collect();
+        assert!(all.len() == 2);
+        assert!(all[0].first_line_number() == 2);
+        assert!(all[1].numbered_lines().collect::<Vec<_>>() == vec![(6, "3"), (7, "x")]);
+
+        let error = all[1]
+            .parse_lines(|line| crate::parse_number::<u32>(line, line))
+            .unwrap_err();
+        assert!(error.line_number() == Some(7));
+    }
+}
@@ -0,0 +1,223 @@
+use aoc::{Answer, Part};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The name of the file in each day's directory that holds its expected answers.
+pub const FILENAME: &str = "answers.txt";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub file: String,
+    pub part: Part,
+    pub answer: Answer,
+}
+
+/// The known-good answers for a day's input files.
+///
+/// The file is a series of blank-line-separated sections, one per input file. Each section starts
+/// with the name of the input file, relative to the day's directory, followed by one line per part
+/// in the form `N: answer`. An answer that spans several lines, like day 10's CRT image, starts on
+/// the line after `N:`.
+///
+/// ```text
+/// input.txt
+/// 1: 70374
+/// 2: 204610
+///
+/// example.txt
+/// 1: 24000
+/// 2: 45000
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct ExpectedAnswers {
+    entries: Vec<Entry>,
+}
+
+impl ExpectedAnswers {
+    pub fn new() -> ExpectedAnswers {
+        ExpectedAnswers {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<ExpectedAnswers, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+        contents
+            .parse()
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    pub fn get(&self, file: &str, part: Part) -> Option<&Answer> {
+        self.entries
+            .iter()
+            .find(|e| e.file == file && e.part == part)
+            .map(|e| &e.answer)
+    }
+
+    /// Set the expected answer for a part of an input file, replacing any answer already there.
+    pub fn set(&mut self, file: &str, part: Part, answer: Answer) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.file == file && e.part == part)
+        {
+            Some(entry) => entry.answer = answer,
+            None => self.entries.push(Entry {
+                file: file.to_string(),
+                part,
+                answer,
+            }),
+        }
+    }
+
+    /// The names of the input files with expected answers, in the order they appear in the file.
+    pub fn files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = Vec::new();
+        for entry in &self.entries {
+            if !files.contains(&entry.file.as_str()) {
+                files.push(&entry.file);
+            }
+        }
+        files
+    }
+}
+
+/// Split a line like `2: 45000` into its part and answer.
+fn parse_part_line(line: &str) -> Option<(Part, &str)> {
+    let (number, answer) = line.split_once(':')?;
+    let part = Part::from_number(number.parse::<u32>().ok()?)?;
+    Some((part, answer.trim()))
+}
+
+impl FromStr for ExpectedAnswers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries: Vec<(String, Part, String)> = Vec::new();
+        let mut file: Option<String> = None;
+        let mut in_multiline_answer = false;
+
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+
+            if line.trim().is_empty() {
+                file = None;
+                in_multiline_answer = false;
+                continue;
+            }
+
+            let Some(current_file) = &file else {
+                if !line.starts_with('#') {
+                    file = Some(line.trim().to_string());
+                }
+                continue;
+            };
+
+            if let Some((part, answer)) = parse_part_line(line) {
+                if entries
+                    .iter()
+                    .any(|(f, p, _)| f == current_file && *p == part)
+                {
+                    return Err(format!(
+                        "line {}: part {} of {} has more than one answer",
+                        line_number, part, current_file
+                    ));
+                }
+                entries.push((current_file.clone(), part, answer.to_string()));
+                in_multiline_answer = answer.is_empty();
+            } else if in_multiline_answer {
+                let (_, _, answer) = entries.last_mut().unwrap();
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(line);
+            } else {
+                return Err(format!("line {}: expected 'N: answer'", line_number));
+            }
+        }
+
+        let entries = entries
+            .into_iter()
+            .map(|(file, part, answer)| Entry {
+                file,
+                part,
+                answer: answer.parse().unwrap(),
+            })
+            .collect();
+
+        Ok(ExpectedAnswers { entries })
+    }
+}
+
+impl fmt::Display for ExpectedAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, file) in self.files().into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}", file)?;
+            for entry in self.entries.iter().filter(|e| e.file == file) {
+                if entry.answer.is_multiline() {
+                    writeln!(f, "{}:", entry.part)?;
+                    writeln!(f, "{}", entry.answer)?;
+                } else {
+                    writeln!(f, "{}: {}", entry.part, entry.answer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExpectedAnswers;
+    use aoc::{Answer, Part};
+
+    const ANSWERS: &str = "\
+# Day 10
+input.txt
+1: 13920
+2:
+##..
+..##
+
+small-example.txt
+1: 0
+";
+
+    #[test]
+    fn parse_single_and_multiline_answers() {
+        let answers: ExpectedAnswers = ANSWERS.parse().unwrap();
+        assert!(answers.get("input.txt", Part::One) == Some(&Answer::from(13920)));
+        assert!(answers.get("input.txt", Part::Two) == Some(&Answer::glyphs("##..\n..##")));
+        assert!(answers.get("small-example.txt", Part::One) == Some(&Answer::from(0)));
+        assert!(answers.get("small-example.txt", Part::Two).is_none());
+        assert!(answers.files() == vec!["input.txt", "small-example.txt"]);
+    }
+
+    #[test]
+    fn duplicate_answers_are_an_error() {
+        let result = "input.txt\n1: 1\n1: 2\n".parse::<ExpectedAnswers>();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn stray_lines_are_an_error() {
+        let result = "input.txt\n1: 1\n##..\n".parse::<ExpectedAnswers>();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn written_answers_parse_back() {
+        let answers: ExpectedAnswers = ANSWERS.parse().unwrap();
+        let reparsed: ExpectedAnswers = answers.to_string().parse().unwrap();
+        assert!(answers == reparsed);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::summary::format_duration;
+use crate::table::{Align, Table};
+use aoc::{Day, ParseError, Part};
+use std::fmt;
+use std::time::Duration;
+
+/// How many times each day is run when no number of iterations is given.
+pub const DEFAULT_ITERATIONS: usize = 10;
+
+/// Statistics about the time taken by repeated runs of one phase of a solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// The sample standard deviation, which is zero when there's only one sample.
+    pub standard_deviation: Duration,
+}
+
+impl Statistics {
+    /// The statistics of some timings, or `None` if there aren't any.
+    pub fn from_samples(samples: &[Duration]) -> Option<Statistics> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let seconds: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        let variance = if seconds.len() > 1 {
+            seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (seconds.len() - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Statistics {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            standard_deviation: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The timings of every phase of a day's solution over repeated runs.
+///
+/// Each run parses the input again, so parsing is timed twice per iteration, once for each part.
+pub struct Benchmark {
+    day: u32,
+    iterations: usize,
+    parse_times: Vec<Duration>,
+    part1_times: Vec<Duration>,
+    part2_times: Vec<Duration>,
+}
+
+impl Benchmark {
+    /// Run both parts of a day's solution `iterations` times, timing each phase.
+    pub fn run(day: &Day, input: &str, iterations: usize) -> Result<Benchmark, ParseError> {
+        let mut benchmark = Benchmark {
+            day: day.number(),
+            iterations,
+            parse_times: Vec::with_capacity(iterations * 2),
+            part1_times: Vec::with_capacity(iterations),
+            part2_times: Vec::with_capacity(iterations),
+        };
+
+        for _ in 0..iterations {
+            for part in Part::all() {
+                let run = day.run(input, *part)?;
+                benchmark.parse_times.push(run.parse_time);
+                match part {
+                    Part::One => benchmark.part1_times.push(run.solve_time),
+                    Part::Two => benchmark.part2_times.push(run.solve_time),
+                }
+            }
+        }
+
+        Ok(benchmark)
+    }
+
+    /// The statistics for each phase, in the order they run.
+    pub fn phases(&self) -> Vec<(&'static str, Option<Statistics>)> {
+        vec![
+            ("Parse", Statistics::from_samples(&self.parse_times)),
+            ("Part 1", Statistics::from_samples(&self.part1_times)),
+            ("Part 2", Statistics::from_samples(&self.part2_times)),
+        ]
+    }
+}
+
+impl fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Day {}, {} iterations", self.day, self.iterations)?;
+
+        let mut table = Table::new(&[
+            ("Phase", Align::Left),
+            ("Min", Align::Right),
+            ("Median", Align::Right),
+            ("Mean", Align::Right),
+            ("Std dev", Align::Right),
+        ]);
+        for (phase, statistics) in self.phases() {
+            let Some(statistics) = statistics else {
+                continue;
+            };
+            table.add_row(vec![
+                phase.to_string(),
+                format_duration(statistics.min),
+                format_duration(statistics.median),
+                format_duration(statistics.mean),
+                format_duration(statistics.standard_deviation),
+            ]);
+        }
+
+        write!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Statistics;
+    use std::time::Duration;
+
+    #[test]
+    fn statistics_of_odd_and_even_samples() {
+        let millis = |ms: &[u64]| -> Vec<Duration> {
+            ms.iter().map(|&m| Duration::from_millis(m)).collect()
+        };
+
+        let odd = Statistics::from_samples(&millis(&[4, 2, 9])).unwrap();
+        assert!(odd.min == Duration::from_millis(2));
+        assert!(odd.median == Duration::from_millis(4));
+        assert!(odd.mean == Duration::from_millis(5));
+
+        let even = Statistics::from_samples(&millis(&[2, 4, 4, 4, 5, 5, 7, 9])).unwrap();
+        assert!(even.median == Duration::from_micros(4500));
+        assert!(even.mean == Duration::from_millis(5));
+        // The squared deviations sum to 32 ms², over 7 degrees of freedom.
+        let expected = (32.0f64 / 7.0).sqrt() / 1000.0;
+        assert!((even.standard_deviation.as_secs_f64() - expected).abs() < 1e-9);
+
+        let single = Statistics::from_samples(&millis(&[3])).unwrap();
+        assert!(single.standard_deviation == Duration::ZERO);
+        assert!(Statistics::from_samples(&[]).is_none());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::bench::Statistics;
+use crate::summary::format_duration;
+use crate::table::{Align, Table};
+use aoc::{Answer, Day, ParseError, Part};
+use std::fmt;
+use std::time::Duration;
+
+/// One implementation of a part, with its answer and how long it took to find it.
+pub struct Implementation {
+    pub name: &'static str,
+    pub answer: Answer,
+    pub solve_time: Statistics,
+}
+
+/// Every implementation of a part of a day's puzzle, run on the same input.
+pub struct Comparison {
+    pub day: u32,
+    pub part: Part,
+    /// The main implementation first, then its alternatives.
+    pub implementations: Vec<Implementation>,
+}
+
+impl Comparison {
+    /// Solve a part with each of its implementations `iterations` times.
+    pub fn run(
+        day: &Day,
+        input: &str,
+        part: Part,
+        iterations: usize,
+    ) -> Result<Comparison, ParseError> {
+        let mut answers: Vec<(&'static str, Answer)> = Vec::new();
+        let mut samples: Vec<Vec<Duration>> = Vec::new();
+
+        for _ in 0..iterations {
+            let runs = day.run_implementations(input, part)?;
+            samples.resize(runs.len(), Vec::with_capacity(iterations));
+            for (i, (name, run)) in runs.into_iter().enumerate() {
+                samples[i].push(run.solve_time);
+                if answers.len() == i {
+                    answers.push((name, run.answer));
+                }
+            }
+        }
+
+        let implementations = answers
+            .into_iter()
+            .zip(samples)
+            .filter_map(|((name, answer), samples)| {
+                Some(Implementation {
+                    name,
+                    answer,
+                    solve_time: Statistics::from_samples(&samples)?,
+                })
+            })
+            .collect();
+
+        Ok(Comparison {
+            day: day.number(),
+            part,
+            implementations,
+        })
+    }
+
+    /// Whether there's anything to compare the main implementation with.
+    pub fn has_alternatives(&self) -> bool {
+        self.implementations.len() > 1
+    }
+
+    /// Whether every implementation found the same answer.
+    pub fn agrees(&self) -> bool {
+        self.implementations
+            .windows(2)
+            .all(|pair| pair[0].answer == pair[1].answer)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {} part {}: {}",
+            self.day,
+            self.part,
+            if self.agrees() {
+                "answers agree"
+            } else {
+                "ANSWERS DIFFER"
+            }
+        )?;
+
+        let main_time = self
+            .implementations
+            .first()
+            .map(|main| main.solve_time.median.as_secs_f64())
+            .unwrap_or_default();
+        let mut table = Table::new(&[
+            ("Implementation", Align::Left),
+            ("Answer", Align::Left),
+            ("Median", Align::Right),
+            ("Speedup", Align::Right),
+        ]);
+        for implementation in &self.implementations {
+            let median = implementation.solve_time.median;
+            // How many times faster than the main implementation, or slower if it's below 1.
+            let speedup = if median > Duration::ZERO {
+                format!("{:.2}x", main_time / median.as_secs_f64())
+            } else {
+                String::new()
+            };
+            table.add_row(vec![
+                implementation.name.to_string(),
+                // Answers drawn in letters take several lines, which won't fit in the table.
+                implementation.answer.to_string().replace('\n', " / "),
+                format_duration(median),
+                speedup,
+            ]);
+        }
+
+        write!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Comparison;
+    use aoc::{Alternative, Answer, Day, ParseError, Part, Solution};
+
+    /// Sums the numbers, with a second way of doing it for each part, one of which is wrong.
+    struct Sums;
+
+    impl Solution for Sums {
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            aoc::parse_lines(input, |line| aoc::parse_number(line, line))
+        }
+
+        fn part1(input: &Self::Input) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> u32 {
+            input.iter().sum()
+        }
+
+        fn alternatives() -> Vec<Alternative<Self::Input>> {
+            vec![
+                Alternative::new("reduce", Part::One, |input| {
+                    input
+                        .iter()
+                        .copied()
+                        .reduce(|a, b| a + b)
+                        .unwrap_or(0)
+                        .into()
+                }),
+                Alternative::new("off by one", Part::Two, |input| {
+                    (input.iter().sum::<u32>() + 1).into()
+                }),
+            ]
+        }
+    }
+
+    #[test]
+    fn implementations_are_compared() {
+        let day = Day::new::<Sums>(1);
+
+        let part1 = Comparison::run(&day, "1\n2\n3\n", Part::One, 3).unwrap();
+        let names: Vec<&str> = part1.implementations.iter().map(|i| i.name).collect();
+        assert!(names == ["main", "reduce"]);
+        assert!(part1.has_alternatives() && part1.agrees());
+        assert!(part1.implementations[1].answer == Answer::from(6));
+
+        let part2 = Comparison::run(&day, "1\n2\n3\n", Part::Two, 1).unwrap();
+        assert!(!part2.agrees());
+        let table = part2.to_string();
+        assert!(table.starts_with("Day 1 part 2: ANSWERS DIFFER\nImplementation  Answer"));
+        assert!(table
+            .lines()
+            .nth(4)
+            .unwrap()
+            .starts_with("off by one      7"));
+
+        assert!(Comparison::run(&day, "x", Part::One, 1).is_err());
+    }
+}
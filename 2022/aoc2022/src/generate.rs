@@ -0,0 +1,111 @@
+use aoc::{Day, Generator};
+
+/// The size of a generated input when no other is given, which is about the size of a real one.
+pub const DEFAULT_SIZE: usize = 10_000;
+
+/// The seed used when no other is given, so inputs are the same from run to run unless asked.
+pub const DEFAULT_SEED: u64 = 2022;
+
+/// Parse a size in bytes, like `5000`, `64k`, `10M` or `2G`.
+pub fn parse_size(size: &str) -> Option<usize> {
+    let (number, multiplier) = match size.char_indices().last()? {
+        (i, 'k' | 'K') => (&size[..i], 1 << 10),
+        (i, 'm' | 'M') => (&size[..i], 1 << 20),
+        (i, 'g' | 'G') => (&size[..i], 1 << 30),
+        _ => (size, 1),
+    };
+    number.parse::<usize>().ok()?.checked_mul(multiplier)
+}
+
+/// The day's generator of the named shape, or its usual one if there's no name.
+pub fn find_generator(day: &Day, shape: Option<&str>) -> Result<Generator, String> {
+    let generators = day.generators();
+    let Some(first) = generators.first() else {
+        return Err(format!("Day {} has no input generators", day.number()));
+    };
+    let Some(shape) = shape else {
+        return Ok(*first);
+    };
+
+    generators
+        .iter()
+        .find(|generator| generator.shape == shape)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "Day {} has no '{}' shape. The shapes are:\n{}",
+                day.number(),
+                shape,
+                shapes(day)
+            )
+        })
+}
+
+/// A line for each shape of input the day can generate, with what it's like.
+pub fn shapes(day: &Day) -> String {
+    let generators = day.generators();
+    let width = generators.iter().map(|g| g.shape.len()).max().unwrap_or(0);
+    generators
+        .iter()
+        .map(|generator| format!("  {:<width$}  {}", generator.shape, generator.description))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_generator, parse_size};
+    use aoc::Part;
+    use std::panic;
+
+    #[test]
+    fn sizes_have_units() {
+        assert!(parse_size("5000") == Some(5000));
+        assert!(parse_size("64k") == Some(65536));
+        assert!(parse_size("10M") == Some(10 << 20));
+        assert!(parse_size("2G") == Some(2 << 30));
+        assert!(parse_size("").is_none());
+        assert!(parse_size("M").is_none());
+        assert!(parse_size("ten").is_none());
+    }
+
+    #[test]
+    fn shapes_are_found_by_name() {
+        let days = crate::registry::days();
+        let day = days.get(13).unwrap();
+        assert!(find_generator(day, None).unwrap().shape == "random");
+        assert!(find_generator(day, Some("deep")).unwrap().shape == "deep");
+        let error = find_generator(day, Some("round")).err().unwrap();
+        assert!(error.starts_with("Day 13 has no 'round' shape. The shapes are:\n  random"));
+    }
+
+    /// Every shape of every day makes inputs the day can parse and solve without panicking, and
+    /// which are the same for the same seed.
+    #[test]
+    fn generated_inputs_are_valid() {
+        for day in crate::registry::days().iter() {
+            for generator in day.generators() {
+                for seed in 0..3 {
+                    let input = generator.generate(seed, 2000);
+                    assert!(input == generator.generate(seed, 2000));
+
+                    for part in Part::all() {
+                        let run = panic::catch_unwind(|| day.run(&input, *part));
+                        let context = format!(
+                            "day {} part {} shape {} seed {}",
+                            day.number(),
+                            part,
+                            generator.shape,
+                            seed
+                        );
+                        match run {
+                            Ok(Ok(_)) => (),
+                            Ok(Err(error)) => panic!("{}: {}", context, error),
+                            Err(_) => panic!("{}: panicked", context),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
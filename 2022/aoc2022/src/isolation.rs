@@ -0,0 +1,502 @@
+use aoc::{Day, ParseError, Part, Run};
+use std::any::Any;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a part of a puzzle can run for before it's given up on, when no timeout is given.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The same stack size as the main thread usually gets, so deeply recursive solutions don't
+/// overflow just because they're running on another thread.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// What a part is solved with.
+#[derive(Clone, Debug)]
+pub enum Input {
+    /// An input that's already been read.
+    Text(Arc<str>),
+    /// A file to read the input from, a line at a time for solutions that can, so it's never held
+    /// whole. Encrypted files are decrypted like [aoc::crypt::open_input] does.
+    File(PathBuf),
+}
+
+impl Input {
+    fn run(&self, day: Day, part: Part) -> Result<Run, ParseError> {
+        match self {
+            Input::Text(input) => day.run(input, part),
+            Input::File(path) => {
+                let mut reader = aoc::crypt::open_input(path)
+                    .map_err(|e| ParseError::new(format!("Unable to read the input: {}", e)))?;
+                let mut runs = day.run_reader(&mut reader, &[part])?;
+                Ok(runs.remove(0))
+            }
+        }
+    }
+}
+
+impl From<Arc<str>> for Input {
+    fn from(input: Arc<str>) -> Input {
+        Input::Text(input)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(input: &str) -> Input {
+        Input::Text(input.into())
+    }
+}
+
+/// How running a part of a day's puzzle went.
+#[derive(Debug)]
+pub enum Outcome {
+    Solved(Run),
+    ParseError(ParseError),
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Outcome {
+    /// A few words about why the part wasn't solved, or `None` if it was.
+    pub fn failure(&self) -> Option<&'static str> {
+        match self {
+            Outcome::Solved(_) => None,
+            Outcome::ParseError(_) => Some("parse error"),
+            Outcome::Panicked(_) => Some("panicked"),
+            Outcome::TimedOut(_) => Some("timed out"),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Solved(run) => write!(f, "{}", run.answer),
+            Outcome::ParseError(error) => write!(f, "{}", error),
+            Outcome::Panicked(message) => write!(f, "panicked: {}", message),
+            Outcome::TimedOut(timeout) => {
+                write!(f, "timed out after {} s", timeout.as_secs_f64())
+            }
+        }
+    }
+}
+
+/// Run a part of a day's puzzle away from the rest of the runner, so that a panic or a runaway
+/// solution doesn't take the rest of the days down with it.
+///
+/// On Linux the part runs in a child process, which is killed if it's still going after `timeout`,
+/// so a part that times out doesn't carry on using the CPU while later parts are timed. Elsewhere
+/// it runs on a thread of its own, which can't be stopped from outside and carries on in the
+/// background until the runner exits.
+pub fn run_isolated(day: Day, input: Input, part: Part, timeout: Duration) -> Outcome {
+    imp::run(day, input, part, timeout)
+}
+
+/// The part in a forked child process, which sends back how it went over a pipe.
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::{panic_message, Input, Outcome, STACK_SIZE};
+    use aoc::{Day, MemoryUsage, ParseError, Part, Run};
+    use std::fs::File;
+    use std::io::{self, Read, Write};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    pub fn run(day: Day, input: Input, part: Part, timeout: Duration) -> Outcome {
+        let mut fds = [0; 2];
+        // SAFETY: fds is valid for writes of both descriptors.
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+            let error = io::Error::last_os_error();
+            return Outcome::Panicked(format!("unable to make a pipe: {}", error));
+        }
+        // SAFETY: pipe2 succeeded, so both descriptors are open and ours alone.
+        let (reader, writer) =
+            unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+        // Anything still buffered would be written by the child as well.
+        let _ = io::stdout().flush();
+        // SAFETY: fork takes no pointers, and the child never returns from this function.
+        let pid = unsafe { libc::fork() };
+        if pid < 0 {
+            let error = io::Error::last_os_error();
+            return Outcome::Panicked(format!("unable to start a process: {}", error));
+        }
+
+        if pid == 0 {
+            drop(reader);
+            let _ = File::from(writer).write_all(solve(day, input, part).as_bytes());
+            let _ = io::stdout().flush();
+            // SAFETY: _exit takes no pointers. The parent's destructors and exit handlers mustn't
+            // run a second time in the child.
+            unsafe { libc::_exit(0) };
+        }
+
+        drop(writer);
+        match read_until(File::from(reader), Instant::now() + timeout) {
+            Some(message) => {
+                let status = wait(pid);
+                decode(&message).unwrap_or_else(|| {
+                    let ending = if libc::WIFSIGNALED(status) {
+                        format!("was killed by signal {}", libc::WTERMSIG(status))
+                    } else {
+                        format!("exited with status {}", libc::WEXITSTATUS(status))
+                    };
+                    Outcome::Panicked(format!("the process {} without an answer", ending))
+                })
+            }
+            None => {
+                // SAFETY: kill takes no pointers, and the child hasn't been waited for yet, so
+                // the pid can't have been reused.
+                unsafe { libc::kill(pid, libc::SIGKILL) };
+                wait(pid);
+                Outcome::TimedOut(timeout)
+            }
+        }
+    }
+
+    /// Run the part, in the child, on a thread with a full sized stack.
+    fn solve(day: Day, input: Input, part: Part) -> String {
+        let spawned = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(move || input.run(day, part));
+        match spawned.map(|handle| handle.join()) {
+            Ok(Ok(result)) => encode(&result),
+            Ok(Err(payload)) => format!("panicked\n{}", panic_message(payload.as_ref())),
+            Err(e) => format!("panicked\nunable to start a thread: {}", e),
+        }
+    }
+
+    /// Everything written to the pipe until the child closes it, or `None` if that's not before
+    /// the deadline.
+    fn read_until(mut reader: File, deadline: Instant) -> Option<String> {
+        let mut message = Vec::new();
+        let mut buffer = [0; 4096];
+        loop {
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            let mut poll_fd = libc::pollfd {
+                fd: reader.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let millis = remaining.as_millis().clamp(1, libc::c_int::MAX as u128);
+            // SAFETY: poll_fd is valid for the length of the call.
+            let ready = unsafe { libc::poll(&mut poll_fd, 1, millis as libc::c_int) };
+            if ready <= 0 {
+                // Interrupted, or the time is up, which is checked at the top of the loop.
+                continue;
+            }
+
+            match reader.read(&mut buffer) {
+                Ok(0) | Err(_) => return Some(String::from_utf8_lossy(&message).into_owned()),
+                Ok(length) => message.extend_from_slice(&buffer[..length]),
+            }
+        }
+    }
+
+    /// Wait for the child to end, and return its status.
+    fn wait(pid: libc::pid_t) -> libc::c_int {
+        let mut status = 0;
+        // SAFETY: status is valid for writes for the length of the call.
+        while unsafe { libc::waitpid(pid, &mut status, 0) } < 0 {
+            if io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+                break;
+            }
+        }
+        status
+    }
+
+    /// How a run went, written for the parent to [decode]. The kind of outcome comes first on a
+    /// line of its own, and whatever can have newlines in it comes last.
+    pub fn encode(result: &Result<Run, ParseError>) -> String {
+        match result {
+            Ok(run) => format!(
+                "solved\n{} {} {} {}\n{}",
+                run.parse_time.as_nanos(),
+                run.solve_time.as_nanos(),
+                encode_memory(run.parse_memory),
+                encode_memory(run.solve_memory),
+                run.answer
+            ),
+            Err(error) => format!(
+                "parse-error\n{}\n{}\n{}\n{}\n{}",
+                optional(error.file()),
+                optional(error.line_number()),
+                optional(error.column()),
+                optional(error.source_line()),
+                error.message()
+            ),
+        }
+    }
+
+    pub fn decode(message: &str) -> Option<Outcome> {
+        let (kind, rest) = message.split_once('\n')?;
+        match kind {
+            "solved" => {
+                let (numbers, answer) = rest.split_once('\n')?;
+                let numbers: Vec<&str> = numbers.split(' ').collect();
+                let [parse_time, solve_time, parse_memory, solve_memory] = numbers[..] else {
+                    return None;
+                };
+                Some(Outcome::Solved(Run {
+                    answer: answer.parse().ok()?,
+                    parse_time: Duration::from_nanos(parse_time.parse().ok()?),
+                    solve_time: Duration::from_nanos(solve_time.parse().ok()?),
+                    parse_memory: decode_memory(parse_memory)?,
+                    solve_memory: decode_memory(solve_memory)?,
+                }))
+            }
+            "parse-error" => {
+                let fields: Vec<&str> = rest.splitn(5, '\n').collect();
+                let [file, line_number, column, source_line, message] = fields[..] else {
+                    return None;
+                };
+                let column = decode_optional(column).map(str::parse).transpose().ok()?;
+                let mut error = match (decode_optional(source_line), column) {
+                    (Some(source_line), Some(column)) => {
+                        ParseError::at(source_line, column, message)
+                    }
+                    _ => ParseError::new(message),
+                };
+                if let Some(line_number) = decode_optional(line_number) {
+                    error = error.with_line_number(line_number.parse().ok()?);
+                }
+                if let Some(file) = decode_optional(file) {
+                    error = error.with_file(file);
+                }
+                Some(Outcome::ParseError(error))
+            }
+            "panicked" => Some(Outcome::Panicked(rest.to_string())),
+            _ => None,
+        }
+    }
+
+    /// A value that might not be there, as `+value` or `-`.
+    fn optional(value: Option<impl ToString>) -> String {
+        value.map_or("-".to_string(), |value| format!("+{}", value.to_string()))
+    }
+
+    fn decode_optional(field: &str) -> Option<&str> {
+        field.strip_prefix('+')
+    }
+
+    fn encode_memory(memory: Option<MemoryUsage>) -> String {
+        memory.map_or("-".to_string(), |memory| {
+            format!(
+                "{},{},{}",
+                memory.allocations, memory.bytes, memory.peak_bytes
+            )
+        })
+    }
+
+    /// The memory a phase used, or `None` if the field isn't one.
+    fn decode_memory(field: &str) -> Option<Option<MemoryUsage>> {
+        if field == "-" {
+            return Some(None);
+        }
+        let counts = field
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<u64>, _>>()
+            .ok()?;
+        let [allocations, bytes, peak_bytes] = counts[..] else {
+            return None;
+        };
+        Some(Some(MemoryUsage {
+            allocations,
+            bytes,
+            peak_bytes,
+        }))
+    }
+}
+
+/// The part on a thread of its own, which is left running if it times out.
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    use super::{panic_message, Input, Outcome, STACK_SIZE};
+    use aoc::{Day, Part};
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::mpsc::{self, RecvTimeoutError};
+    use std::thread;
+    use std::time::Duration;
+
+    pub fn run(day: Day, input: Input, part: Part, timeout: Duration) -> Outcome {
+        let (sender, receiver) = mpsc::channel();
+
+        let spawned = thread::Builder::new()
+            .name(format!("day {} part {}", day.number(), part))
+            .stack_size(STACK_SIZE)
+            .spawn(move || {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| input.run(day, part)));
+                // Nobody is listening any more if the part took too long.
+                let _ = sender.send(result);
+            });
+
+        if let Err(e) = spawned {
+            return Outcome::Panicked(format!("unable to start a thread: {}", e));
+        }
+
+        match receiver.recv_timeout(timeout) {
+            Ok(Ok(Ok(run))) => Outcome::Solved(run),
+            Ok(Ok(Err(error))) => Outcome::ParseError(error),
+            Ok(Err(payload)) => Outcome::Panicked(panic_message(payload.as_ref())),
+            Err(RecvTimeoutError::Timeout) => Outcome::TimedOut(timeout),
+            Err(RecvTimeoutError::Disconnected) => {
+                Outcome::Panicked("the thread ended without an answer".to_string())
+            }
+        }
+    }
+}
+
+/// The message a panic was made with, which is almost always a string.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run_isolated, Input, Outcome};
+    use aoc::{Answer, Day, ParseError, Part, Solution};
+    use std::fs;
+    use std::time::Duration;
+
+    /// Part 1 counts the lines, and part 2 does whatever the first line says.
+    struct Misbehaving;
+
+    impl Solution for Misbehaving {
+        type Input = Vec<String>;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            if input.is_empty() {
+                return Err(ParseError::new("Empty input"));
+            }
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part1(input: &Self::Input) -> usize {
+            input.len()
+        }
+
+        fn part2(input: &Self::Input) -> usize {
+            match input[0].as_str() {
+                "panic" => panic!("Told to panic"),
+                "loop" => loop {
+                    std::thread::sleep(Duration::from_millis(10));
+                },
+                line => {
+                    // Write to a file after the timeout, if there's a file to write to.
+                    if let Some(path) = line.strip_prefix("late ") {
+                        std::thread::sleep(Duration::from_millis(400));
+                        fs::write(path, "still running").unwrap();
+                    }
+                    0
+                }
+            }
+        }
+    }
+
+    fn run(input: &str, part: Part) -> Outcome {
+        let day = Day::new::<Misbehaving>(1);
+        run_isolated(day, input.into(), part, Duration::from_millis(200))
+    }
+
+    #[test]
+    fn answers_are_passed_back() {
+        let Outcome::Solved(run) = run("a\nb", Part::One) else {
+            panic!("Expected an answer");
+        };
+        assert!(run.answer == Answer::from(2));
+    }
+
+    #[test]
+    fn files_are_read_by_the_part() {
+        let path = std::env::temp_dir().join(format!("aoc-isolation-{}.txt", std::process::id()));
+        fs::write(&path, "a\nb\nc\n").unwrap();
+        let day = Day::new::<Misbehaving>(1);
+        let outcome = run_isolated(
+            day,
+            Input::File(path.clone()),
+            Part::One,
+            Duration::from_secs(5),
+        );
+        assert!(matches!(outcome, Outcome::Solved(run) if run.answer == Answer::from(3)));
+
+        fs::remove_file(&path).unwrap();
+        let outcome = run_isolated(day, Input::File(path), Part::One, Duration::from_secs(5));
+        assert!(
+            matches!(outcome, Outcome::ParseError(error) if error.message().starts_with("Unable to read"))
+        );
+    }
+
+    #[test]
+    fn failures_are_caught() {
+        assert!(matches!(run("", Part::One), Outcome::ParseError(_)));
+        assert!(
+            matches!(run("panic", Part::Two), Outcome::Panicked(message) if message == "Told to panic")
+        );
+        assert!(matches!(run("loop", Part::Two), Outcome::TimedOut(_)));
+
+        // Part 1 is fine however part 2 would go.
+        assert!(matches!(run("panic", Part::One), Outcome::Solved(_)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn timed_out_parts_are_stopped() {
+        let path = std::env::temp_dir().join(format!("aoc-isolation-{}", std::process::id()));
+        let input = format!("late {}", path.display());
+        assert!(matches!(run(&input, Part::Two), Outcome::TimedOut(_)));
+
+        std::thread::sleep(Duration::from_millis(600));
+        assert!(!path.exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn outcomes_survive_the_pipe() {
+        use super::imp::{decode, encode};
+        use aoc::{MemoryUsage, Run};
+
+        let run = Run {
+            answer: Answer::glyphs("#..\n.#."),
+            parse_time: Duration::from_nanos(1234),
+            solve_time: Duration::from_nanos(5678),
+            parse_memory: None,
+            solve_memory: Some(MemoryUsage {
+                allocations: 1,
+                bytes: 2,
+                peak_bytes: 3,
+            }),
+        };
+        let Some(Outcome::Solved(decoded)) = decode(&encode(&Ok(run.clone()))) else {
+            panic!("Expected an answer");
+        };
+        assert!(decoded.answer == run.answer);
+        assert!(decoded.solve_time == run.solve_time);
+        assert!(decoded.parse_memory.is_none());
+        assert!(decoded.solve_memory == run.solve_memory);
+
+        let error = ParseError::at_token("move 1 from x", "x", "Expected a stack\non two lines")
+            .with_line_number(3)
+            .with_file("input.txt");
+        let Some(Outcome::ParseError(decoded)) = decode(&encode(&Err(error.clone()))) else {
+            panic!("Expected a parse error");
+        };
+        assert!(decoded == error);
+
+        let error = ParseError::new("Empty input");
+        assert!(
+            matches!(decode(&encode(&Err(error.clone()))), Some(Outcome::ParseError(e)) if e == error)
+        );
+        assert!(decode("nonsense").is_none());
+    }
+}
@@ -0,0 +1,17 @@
+pub mod answers;
+pub mod bench;
+pub mod compare;
+pub mod file;
+pub mod generate;
+pub mod isolation;
+pub mod puzzle;
+pub mod registry;
+pub mod report;
+pub mod scaffold;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod store;
+pub mod summary;
+pub mod table;
+pub mod verify;
+pub mod watch;
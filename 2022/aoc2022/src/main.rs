@@ -1,51 +1,840 @@
-use std::env;
-use std::path::Path;
-
-mod day1;
-mod day10;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod file;
-mod grid;
+use aoc::crypt::Key;
+use aoc::{Day, Format, InputOptions, InputSource, Part, Registry};
+use aoc2022::bench::{self, Benchmark};
+use aoc2022::compare::Comparison;
+use aoc2022::generate;
+use aoc2022::isolation::{self, Input, Outcome};
+use aoc2022::report::{self, DayReport};
+use aoc2022::store::{self, AnswerStore};
+use aoc2022::summary::Summary;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, process};
 
-fn main() {
-    let days = [
-        day1::main,
-        day2::main,
-        day3::main,
-        day4::main,
-        day5::main,
-        day6::main,
-        day7::main,
-        day8::main,
-        day9::main,
-        day10::main,
-    ];
-
-    let args: Vec<String> = env::args().collect();
-
-    let datafile_path = Path::new(&args[1]);
-    if !datafile_path.is_dir() {
-        println!("{} is not a path to a directory!", datafile_path.display());
-        return;
+/// The year of the puzzles this runner solves, and whose inputs are in the data directory.
+#[cfg(feature = "fetch")]
+const YEAR: u32 = 2022;
+
+fn exit_with_usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
+
+/// Read the input for a day, reporting why if it can't be read.
+fn read_input(options: &InputOptions, day: &Day) -> Option<(InputSource, String)> {
+    let source = match options.source_for(day) {
+        Ok(source) => source,
+        Err(message) => {
+            eprintln!("Day {}: {}", day.number(), message);
+            return None;
+        }
+    };
+
+    match source.read() {
+        Ok(input) => Some((source, input)),
+        Err(e) => {
+            eprintln!("Day {}: unable to read {}: {}", day.number(), source, e);
+            None
+        }
+    }
+}
+
+/// The input for a day as the runner solves it, with its hash, reporting why if it can't be read.
+/// Files are hashed as they're read, and read again a line at a time by each part, so they're
+/// never held whole. Standard input can only be read once, so it is.
+fn input_to_solve(options: &InputOptions, day: &Day) -> Option<(InputSource, Input, String)> {
+    let source = match options.source_for(day) {
+        Ok(source) => source,
+        Err(message) => {
+            eprintln!("Day {}: {}", day.number(), message);
+            return None;
+        }
+    };
+
+    let read = match &source {
+        InputSource::File(path) => source
+            .open()
+            .and_then(store::read_input_hash)
+            .map(|hash| (Input::File(path.clone()), hash)),
+        InputSource::Stdin => source.read().map(|input| {
+            let hash = store::input_hash(&input);
+            (Input::Text(input.into()), hash)
+        }),
+    };
+    match read {
+        Ok((input, hash)) => Some((source, input, hash)),
+        Err(e) => {
+            eprintln!("Day {}: unable to read {}: {}", day.number(), source, e);
+            None
+        }
+    }
+}
+
+/// The `--timeout SECONDS` option, which is how long each part can run for.
+fn take_timeout(args: &mut Vec<String>) -> Duration {
+    match aoc::take_option(args, "--timeout") {
+        Ok(None) => isolation::DEFAULT_TIMEOUT,
+        Ok(Some(seconds)) => match seconds.parse::<f64>() {
+            Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Duration::from_secs_f64(seconds),
+            _ => exit_with_usage_error("--timeout needs a number of seconds greater than 0"),
+        },
+        Err(message) => exit_with_usage_error(&message),
+    }
+}
+
+/// The `--iterations N` option, which is how many times each day is run to time it.
+fn take_iterations(args: &mut Vec<String>) -> usize {
+    match aoc::take_option(args, "--iterations") {
+        Ok(None) => bench::DEFAULT_ITERATIONS,
+        Ok(Some(n)) => match n.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => exit_with_usage_error("--iterations needs a number greater than 0"),
+        },
+        Err(message) => exit_with_usage_error(&message),
+    }
+}
+
+/// How the days are run, from the command line options.
+struct Settings {
+    format: Format,
+    timeout: Duration,
+    /// Whether answers from the store are used, when there are any for the same input.
+    cached: bool,
+    /// Whether answers that differ from the stored ones replace them, rather than being reported
+    /// as regressions.
+    accept: bool,
+    revision: String,
+}
+
+/// Run both parts of a day, each in isolation. Parts that panic or run for too long, or that have
+/// no input to run on, are reported and added to the summary as failures. Answers are added to the store, and the number of answers
+/// that differ from the ones stored for the same input is returned.
+fn run_day(
+    options: &InputOptions,
+    day: &Day,
+    settings: &Settings,
+    store: &mut AnswerStore,
+    summary: &mut Summary,
+) -> usize {
+    let Some((source, input, input_hash)) = input_to_solve(options, day) else {
+        summary.add_day_failure(day.number(), "no input");
+        return 0;
+    };
+    let mut regressions = 0;
+
+    for part in Part::all() {
+        if settings.cached {
+            if let Some(record) = store.get(day.number(), *part, &input_hash) {
+                let run = record.to_run();
+                settings.format.print_run(day.number(), *part, &run);
+                summary.add(day.number(), *part, run);
+                continue;
+            }
+        }
+
+        let outcome = isolation::run_isolated(*day, input.clone(), *part, settings.timeout);
+        match outcome {
+            Outcome::Solved(run) => {
+                settings.format.print_run(day.number(), *part, &run);
+                match store.record(
+                    day.number(),
+                    *part,
+                    &input_hash,
+                    &run,
+                    &settings.revision,
+                    settings.accept,
+                ) {
+                    Ok(None) => (),
+                    Ok(Some(regression)) => {
+                        eprintln!("{}", regression);
+                        regressions += 1;
+                    }
+                    Err(message) => eprintln!("{}", message),
+                }
+                summary.add(day.number(), *part, run);
+            }
+            Outcome::ParseError(error) => {
+                eprintln!("{}", error.with_file(source.to_string()));
+                summary.add_failure(day.number(), *part, "parse error");
+                return regressions;
+            }
+            Outcome::Panicked(_) | Outcome::TimedOut(_) => {
+                eprintln!("Day {} part {}: {}", day.number(), part, outcome);
+                if let Some(failure) = outcome.failure() {
+                    summary.add_failure(day.number(), *part, failure);
+                }
+            }
+        }
+    }
+
+    regressions
+}
+
+/// The day numbers in the arguments, which shouldn't have anything else left in them.
+fn day_numbers(args: &[String]) -> Vec<u32> {
+    args.iter()
+        .map(|arg| {
+            arg.parse::<u32>().unwrap_or_else(|_| {
+                exit_with_usage_error(&format!("Unexpected argument '{}'", arg))
+            })
+        })
+        .collect()
+}
+
+/// Benchmark the days numbered in `args`, which may also have an `--iterations N` option.
+fn bench(options: &InputOptions, days: &Registry, mut args: Vec<String>) {
+    let iterations = take_iterations(&mut args);
+
+    let day_numbers = day_numbers(&args);
+    if day_numbers.is_empty() {
+        exit_with_usage_error("Which days should be benchmarked?");
+    }
+
+    for day_number in day_numbers {
+        let Some(day) = days.get(day_number) else {
+            eprintln!("No solution for day {}", day_number);
+            continue;
+        };
+
+        let Some((source, input)) = read_input(options, day) else {
+            continue;
+        };
+
+        match Benchmark::run(day, &input, iterations) {
+            Ok(benchmark) => println!("{}", benchmark),
+            Err(error) => eprintln!("{}", error.with_file(source.to_string())),
+        }
+    }
+}
+
+/// Run every implementation of each part of the days numbered in `args`, or of every day with
+/// alternatives if there aren't any, and show their answers and timings side by side. Exits with
+/// an error if any implementations disagree. An `--iterations N` option sets how many times each is
+/// run.
+fn compare(options: &InputOptions, days: &Registry, mut args: Vec<String>) {
+    let iterations = take_iterations(&mut args);
+
+    let day_numbers = day_numbers(&args);
+    let mut disagreements = 0;
+    for day in days.iter() {
+        let named = day_numbers.contains(&day.number());
+        if !day_numbers.is_empty() && !named {
+            continue;
+        }
+
+        let Some((source, input)) = read_input(options, day) else {
+            continue;
+        };
+
+        for part in Part::all() {
+            match Comparison::run(day, &input, *part, iterations) {
+                Ok(comparison) if comparison.has_alternatives() => {
+                    println!("{}", comparison);
+                    if !comparison.agrees() {
+                        disagreements += 1;
+                    }
+                }
+                Ok(_) if named => {
+                    println!("Day {} part {}: no alternatives\n", day.number(), part)
+                }
+                Ok(_) => (),
+                Err(error) => {
+                    eprintln!("{}", error.with_file(source.to_string()));
+                    break;
+                }
+            }
+        }
+    }
+
+    if disagreements > 0 {
+        eprintln!(
+            "{} part(s) have implementations that disagree",
+            disagreements
+        );
+        process::exit(1);
+    }
+}
+
+/// Check the answers of the days numbered in `args`, or every day if there aren't any.
+fn verify(options: &InputOptions, days: &Registry, args: Vec<String>) {
+    let data_path = options
+        .data_dir()
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    let day_numbers = day_numbers(&args);
+
+    let mut mismatches = 0;
+    for day in days.iter() {
+        if day_numbers.is_empty() || day_numbers.contains(&day.number()) {
+            mismatches += aoc2022::verify::verify_day(&data_path, day);
+        }
+    }
+
+    if mismatches > 0 {
+        println!("{} answers did not match", mismatches);
+        process::exit(1);
+    }
+}
+
+/// Watch the inputs and examples of the day numbered in `args`, or just the one given by `--input`
+/// or `--example`. `args` may also have a `--part N` option to only run one part.
+fn watch(options: &InputOptions, days: &Registry, mut args: Vec<String>, timeout: Duration) {
+    let parts = match aoc::take_option(&mut args, "--part") {
+        Ok(None) => Part::all(),
+        Ok(Some(n)) => match n.parse().ok().and_then(Part::from_number) {
+            Some(Part::One) => &[Part::One],
+            Some(Part::Two) => &[Part::Two],
+            None => exit_with_usage_error("--part needs to be 1 or 2"),
+        },
+        Err(message) => exit_with_usage_error(&message),
+    };
+
+    let [day_number] = day_numbers(&args)[..] else {
+        exit_with_usage_error("Which day should be watched?");
+    };
+    let Some(day) = days.get(day_number) else {
+        exit_with_usage_error(&format!("No solution for day {}", day_number));
+    };
+
+    let paths = if options.input.is_some() || options.example.is_some() {
+        match options.source_for(day) {
+            Ok(InputSource::File(path)) => vec![path],
+            Ok(InputSource::Stdin) => exit_with_usage_error("Standard input can't be watched"),
+            Err(message) => exit_with_usage_error(&message),
+        }
+    } else {
+        let day_dir = options
+            .day_dir(day)
+            .unwrap_or_else(|message| exit_with_usage_error(&message));
+        match aoc2022::watch::day_files(&day_dir) {
+            Ok(paths) if !paths.is_empty() => paths,
+            Ok(_) => exit_with_usage_error(&format!(
+                "There are no inputs or examples in {}",
+                day_dir.display()
+            )),
+            Err(e) => {
+                eprintln!("Unable to read {}: {}", day_dir.display(), e);
+                process::exit(1);
+            }
+        }
+    };
+
+    if let Err(e) = aoc2022::watch::watch(day, &paths, parts, timeout) {
+        eprintln!("Unable to watch day {}: {}", day.number(), e);
+        process::exit(1);
+    }
+}
+
+/// Download the input for `<year> <day>` into the day's directory, unless it's already there.
+#[cfg(feature = "fetch")]
+fn fetch(options: &InputOptions, args: Vec<String>) {
+    let [year, day] = day_numbers(&args)[..] else {
+        exit_with_usage_error("Usage: fetch <year> <day>");
+    };
+    if !(1..=25).contains(&day) {
+        exit_with_usage_error("Expected a day from 1 to 25");
+    }
+    // The day directories have no year in their names, so another year's input would be saved
+    // over this year's.
+    if year != YEAR {
+        exit_with_usage_error(&format!(
+            "The data directory is for {}, so it can't hold inputs for {}",
+            YEAR, year
+        ));
+    }
+
+    let fetcher = aoc::Config::load()
+        .and_then(|config| aoc::Fetcher::from_config(&config))
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    let path = options
+        .data_dir()
+        .unwrap_or_else(|message| exit_with_usage_error(&message))
+        .join(format!("day{:02}", day))
+        .join(aoc::INPUT_FILENAME);
+
+    match fetcher.fetch(year, day, &path) {
+        Ok(aoc::Fetched::Downloaded) => println!("Downloaded {}", path.display()),
+        Ok(aoc::Fetched::Cached) => println!("Already downloaded {}", path.display()),
+        Err(error) => {
+            eprintln!(
+                "Unable to fetch the input for {} day {}: {}",
+                year, day, error
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Save the examples and example answers from a saved puzzle page, given as `<day> <page.html>`,
+/// into the day's directory. An `--answers-example N` option says which example the answers are
+/// for, when it isn't the first.
+fn extract(options: &InputOptions, mut args: Vec<String>) {
+    let answers_example = match aoc::take_option(&mut args, "--answers-example") {
+        Ok(None) => 1,
+        Ok(Some(n)) => n
+            .parse()
+            .unwrap_or_else(|_| exit_with_usage_error("--answers-example needs a number")),
+        Err(message) => exit_with_usage_error(&message),
+    };
+
+    let [day, page_path] = &args[..] else {
+        exit_with_usage_error("Usage: extract <day> <page.html> [--answers-example N]");
+    };
+    let day = day_numbers(std::slice::from_ref(day))[0];
+
+    let html = std::fs::read_to_string(page_path).unwrap_or_else(|e| {
+        eprintln!("Unable to read {}: {}", page_path, e);
+        process::exit(1);
+    });
+    let page = aoc2022::puzzle::PuzzlePage::parse(&html);
+    if page.examples.is_empty() {
+        eprintln!("There are no examples in {}", page_path);
+        process::exit(1);
+    }
+
+    let day_dir = options
+        .data_dir()
+        .unwrap_or_else(|message| exit_with_usage_error(&message))
+        .join(format!("day{:02}", day));
+    if let Err(message) = page.save(&day_dir, answers_example) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+/// Make a crate for the day in `args` and register it here. A `--geometry` flag adds a dependency
+/// on the geometry crate.
+fn new_day(mut args: Vec<String>) {
+    let geometry = aoc::take_flag(&mut args, "--geometry");
+    let [day] = day_numbers(&args)[..] else {
+        exit_with_usage_error("Usage: new <day> [--geometry]");
+    };
+    if !(1..=25).contains(&day) {
+        exit_with_usage_error("Expected a day from 1 to 25");
+    }
+
+    match aoc2022::scaffold::new_day(&aoc2022::scaffold::default_crates_dir(), day, geometry) {
+        Ok(files) => {
+            for file in files {
+                println!("Wrote {}", file.display());
+            }
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
+
+/// The puzzle inputs anywhere in the data directory, which are the files named `input.txt` or
+/// `dayN-input.txt`, or the same with `.enc` on the end if `encrypted`. Examples, like
+/// `input-head-2.txt` or `day7-test-input.txt`, aren't inputs. Build output in `target`
+/// directories and anything hidden is skipped.
+fn input_files(options: &InputOptions, encrypted: bool) -> Vec<PathBuf> {
+    let data_dir = options
+        .data_dir()
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+
+    let mut files = Vec::new();
+    if let Err(e) = find_input_files(&data_dir, encrypted, &mut files) {
+        eprintln!("Unable to read {}: {}", data_dir.display(), e);
+        process::exit(1);
+    }
+    files.sort();
+    files
+}
+
+fn find_input_files(directory: &Path, encrypted: bool, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(directory)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                find_input_files(&entry.path(), encrypted, files)?;
+            }
+            continue;
+        }
+
+        let plain_name = match name.strip_suffix(".enc") {
+            Some(plain_name) if encrypted => plain_name,
+            None if !encrypted => &name,
+            _ => continue,
+        };
+        if is_input_name(plain_name) {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+fn is_input_name(name: &str) -> bool {
+    let day_number = name
+        .strip_prefix("day")
+        .and_then(|rest| rest.strip_suffix("-input.txt"));
+    name == aoc::INPUT_FILENAME
+        || day_number.is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Encrypt the files in `args`, or every day's puzzle inputs, so they can be committed. Each
+/// `FILE` is replaced by `FILE.enc`, which the input loader decrypts. A key is made if there isn't
+/// one yet.
+fn encrypt(options: &InputOptions, args: Vec<String>) {
+    let files = if args.is_empty() {
+        input_files(options, false)
+    } else {
+        args.iter().map(PathBuf::from).collect()
+    };
+
+    let (key, new_key_path) =
+        Key::load_or_create().unwrap_or_else(|message| exit_with_usage_error(&message));
+    if let Some(path) = new_key_path {
+        println!(
+            "Made a new key in {}. Keep a copy of it somewhere safe: the inputs can't be read \
+             without it",
+            path.display()
+        );
+    }
+
+    let mut failed = false;
+    for path in files {
+        let encrypted_path = aoc::crypt::encrypted_path(&path);
+        let result = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))
+            .and_then(|input| {
+                std::fs::write(&encrypted_path, key.encrypt(&input))
+                    .map_err(|e| format!("Unable to write {}: {}", encrypted_path.display(), e))
+            })
+            .and_then(|_| {
+                std::fs::remove_file(&path)
+                    .map_err(|e| format!("Unable to remove {}: {}", path.display(), e))
+            });
+        match result {
+            Ok(()) => println!("Encrypted {}", encrypted_path.display()),
+            Err(message) => {
+                eprintln!("{}", message);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
     }
+}
+
+/// Decrypt the files in `args`, or every day's encrypted puzzle inputs, next to the encrypted
+/// ones, for tools that can't read them encrypted. Files that are already there are left alone.
+fn decrypt(options: &InputOptions, args: Vec<String>) {
+    let files = if args.is_empty() {
+        input_files(options, true)
+    } else {
+        args.iter().map(PathBuf::from).collect()
+    };
 
-    args.iter().skip(2).for_each(|arg| {
-        if let Ok(module_number) = usize::from_str_radix(arg, 10) {
-            println!("----- Day {} -----", module_number);
-            if let Some(datafile) = datafile_path
-                .join(format!("day{}-input.txt", module_number))
-                .as_path()
-                .to_str()
-            {
-                days[module_number - 1](datafile).expect("Unable to process day1 data file");
+    let mut failed = false;
+    for path in files {
+        if !aoc::crypt::is_encrypted_path(&path) {
+            eprintln!("{} isn't an encrypted file", path.display());
+            failed = true;
+            continue;
+        }
+        let plain_path = path.with_extension("");
+        if plain_path.exists() {
+            println!("{} is already there", plain_path.display());
+            continue;
+        }
+
+        let result = aoc::crypt::read_input(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|input| {
+                std::fs::write(&plain_path, input)
+                    .map_err(|e| format!("Unable to write {}: {}", plain_path.display(), e))
+            });
+        match result {
+            Ok(()) => println!("Decrypted {}", plain_path.display()),
+            Err(message) => {
+                eprintln!("{}", message);
+                failed = true;
             }
         }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Run every day and write a table of how each is doing into the README, between the report
+/// markers. `--readme PATH` names another README, and `--iterations N` sets how many times each
+/// day is run to time it.
+fn report(options: &InputOptions, days: &Registry, mut args: Vec<String>, timeout: Duration) {
+    let iterations = take_iterations(&mut args);
+    let crates_dir = aoc2022::scaffold::default_crates_dir();
+    let readme_path = match aoc::take_option(&mut args, "--readme") {
+        Ok(Some(path)) => PathBuf::from(path),
+        Ok(None) => crates_dir.parent().unwrap_or(&crates_dir).join("README.md"),
+        Err(message) => exit_with_usage_error(&message),
+    };
+    if !args.is_empty() {
+        exit_with_usage_error("Usage: report [--readme PATH] [--iterations N]");
+    }
+    let data_dir = options
+        .data_dir()
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+
+    let readme_dir = readme_path.parent().unwrap_or(Path::new(""));
+    let reports: Vec<DayReport> = days
+        .iter()
+        .map(|day| {
+            let crate_dir = crates_dir.join(day.directory_name());
+            let crate_link = crate_dir.strip_prefix(readme_dir).unwrap_or(&crate_dir);
+            let report = DayReport::run(
+                day,
+                &data_dir.join(day.directory_name()),
+                &crate_link.display().to_string(),
+                iterations,
+                timeout,
+            );
+            println!(
+                "Day {}: {}",
+                day.number(),
+                match report.status {
+                    report::Status::Solved => "solved",
+                    report::Status::Unverified => "solved, but no expected answers to check",
+                    report::Status::Unsolved => "NOT SOLVED",
+                }
+            );
+            report
+        })
+        .collect();
+
+    let readme = match std::fs::read_to_string(&readme_path) {
+        Ok(readme) => readme,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            eprintln!("Unable to read {}: {}", readme_path.display(), e);
+            process::exit(1);
+        }
+    };
+    let updated = report::insert_report(&readme, &report::table(&reports));
+    if updated == readme {
+        println!("{} is up to date", readme_path.display());
+    } else if let Err(e) = std::fs::write(&readme_path, updated) {
+        eprintln!("Unable to write {}: {}", readme_path.display(), e);
+        process::exit(1);
+    } else {
+        println!("Wrote the report into {}", readme_path.display());
+    }
+}
+
+/// Print a made-up input for the day in `args`, of the shape named by `--shape NAME`, about
+/// `--size BYTES` long and made from `--seed N`. The same options always make the same input.
+/// `--shapes` lists the shapes instead.
+fn generate_input(days: &Registry, mut args: Vec<String>) {
+    let list_shapes = aoc::take_flag(&mut args, "--shapes");
+    let shape = aoc::take_option(&mut args, "--shape")
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    let size = match aoc::take_option(&mut args, "--size") {
+        Ok(None) => generate::DEFAULT_SIZE,
+        Ok(Some(size)) => generate::parse_size(&size).unwrap_or_else(|| {
+            exit_with_usage_error("--size needs a number of bytes, like 5000, 64k or 10M")
+        }),
+        Err(message) => exit_with_usage_error(&message),
+    };
+    let seed = match aoc::take_option(&mut args, "--seed") {
+        Ok(None) => generate::DEFAULT_SEED,
+        Ok(Some(seed)) => seed
+            .parse()
+            .unwrap_or_else(|_| exit_with_usage_error("--seed needs a number")),
+        Err(message) => exit_with_usage_error(&message),
+    };
+
+    let [day_number] = day_numbers(&args)[..] else {
+        exit_with_usage_error(
+            "Usage: gen <day> [--shape NAME] [--size BYTES] [--seed N] | gen <day> --shapes",
+        );
+    };
+    let Some(day) = days.get(day_number) else {
+        exit_with_usage_error(&format!("No solution for day {}", day_number));
+    };
+
+    if list_shapes {
+        println!("{}", generate::shapes(day));
+        return;
+    }
+    let generator = generate::find_generator(day, shape.as_deref())
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+
+    let input = generator.generate(seed, size);
+    if let Err(e) = std::io::stdout().lock().write_all(input.as_bytes()) {
+        eprintln!("Unable to write the input: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Answer requests to solve puzzles over HTTP on localhost, on port `--port N` or the default.
+#[cfg(feature = "serve")]
+fn serve(days: Registry, mut args: Vec<String>, timeout: Duration) {
+    let port = match aoc::take_option(&mut args, "--port") {
+        Ok(None) => aoc2022::serve::DEFAULT_PORT,
+        Ok(Some(port)) => port
+            .parse()
+            .unwrap_or_else(|_| exit_with_usage_error("--port needs a port number")),
+        Err(message) => exit_with_usage_error(&message),
+    };
+    if !args.is_empty() {
+        exit_with_usage_error("Usage: serve [--port N]");
+    }
+
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|e| {
+        eprintln!("Unable to listen on port {}: {}", port, e);
+        process::exit(1);
     });
+    println!(
+        "Listening on http://127.0.0.1:{}/, POST an input to /2022/<day>/<part> to solve it",
+        port
+    );
+
+    if let Err(e) = aoc2022::serve::serve(listener, std::sync::Arc::new(days), timeout) {
+        eprintln!("Stopped serving: {}", e);
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let days = aoc2022::registry::days();
+
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let format =
+        Format::take_from_args(&mut args).unwrap_or_else(|message| exit_with_usage_error(&message));
+    let mut options = InputOptions::take_from_args(&mut args)
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    let timeout = take_timeout(&mut args);
+    let level = aoc::log::Level::take_from_args(&mut args)
+        .unwrap_or_else(|message| exit_with_usage_error(&message));
+    aoc::log::set_level(level);
+
+    match args.first().map(|s| s.as_str()) {
+        Some("bench") => {
+            bench(&options, &days, args.split_off(1));
+            return;
+        }
+        Some("compare") => {
+            compare(&options, &days, args.split_off(1));
+            return;
+        }
+        Some("decrypt") => {
+            decrypt(&options, args.split_off(1));
+            return;
+        }
+        Some("encrypt") => {
+            encrypt(&options, args.split_off(1));
+            return;
+        }
+        Some("extract") => {
+            extract(&options, args.split_off(1));
+            return;
+        }
+        #[cfg(feature = "fetch")]
+        Some("fetch") => {
+            fetch(&options, args.split_off(1));
+            return;
+        }
+        #[cfg(not(feature = "fetch"))]
+        Some("fetch") => {
+            exit_with_usage_error("fetch needs the runner to be built with --features fetch")
+        }
+        Some("gen") => {
+            generate_input(&days, args.split_off(1));
+            return;
+        }
+        Some("new") => {
+            new_day(args.split_off(1));
+            return;
+        }
+        Some("report") => {
+            report(&options, &days, args.split_off(1), timeout);
+            return;
+        }
+        #[cfg(feature = "serve")]
+        Some("serve") => {
+            serve(days, args.split_off(1), timeout);
+            return;
+        }
+        #[cfg(not(feature = "serve"))]
+        Some("serve") => {
+            exit_with_usage_error("serve needs the runner to be built with --features serve")
+        }
+        Some("verify") => {
+            verify(&options, &days, args.split_off(1));
+            return;
+        }
+        Some("watch") => {
+            watch(&options, &days, args.split_off(1), timeout);
+            return;
+        }
+        _ => (),
+    }
+
+    options.take_positional_data_dir(&mut args);
+    let settings = Settings {
+        format,
+        timeout,
+        cached: aoc::take_flag(&mut args, "--cached"),
+        accept: aoc::take_flag(&mut args, "--accept"),
+        revision: store::git_revision(),
+    };
+    let store_path = options
+        .data_dir()
+        .unwrap_or_else(|message| exit_with_usage_error(&message))
+        .join(store::FILENAME);
+    let mut store =
+        AnswerStore::open(&store_path).unwrap_or_else(|message| exit_with_usage_error(&message));
+
+    let mut summary = Summary::new();
+    let mut regressions = 0;
+
+    if aoc::take_flag(&mut args, "--all") {
+        for day in days.iter() {
+            if format == Format::Text {
+                println!("----- Day {} -----", day.number());
+            }
+            regressions += run_day(&options, day, &settings, &mut store, &mut summary);
+        }
+
+        if format == Format::Text {
+            println!("----- Summary -----");
+            println!("{}", summary);
+        }
+    } else {
+        for day_number in day_numbers(&args) {
+            if format == Format::Text {
+                println!("----- Day {} -----", day_number);
+            }
+
+            let Some(day) = days.get(day_number) else {
+                eprintln!("No solution for day {}", day_number);
+                summary.add_day_failure(day_number, "no solution");
+                continue;
+            };
+
+            regressions += run_day(&options, day, &settings, &mut store, &mut summary);
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!(
+            "{} answer(s) changed from the ones stored for the same input, see REGRESSION above. \
+             Run again with --accept if the new answers are right",
+            regressions
+        );
+        process::exit(1);
+    }
+
+    if summary.failures() > 0 {
+        // On Linux timed-out parts have been killed already, but elsewhere they're threads that
+        // are still running, and exiting is the only way to stop them.
+        eprintln!("{} parts failed", summary.failures());
+        process::exit(1);
+    }
 }
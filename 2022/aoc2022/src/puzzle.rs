@@ -0,0 +1,240 @@
+use crate::answers::{self, ExpectedAnswers};
+use crate::report;
+use aoc::{Answer, Part};
+use std::fs;
+use std::path::Path;
+
+/// The parts of a saved puzzle page that are useful for checking a solution: the title, the example
+/// blocks and the answers to the examples.
+///
+/// The page has an `<article class="day-desc">` for each part that has been unlocked. Examples are
+/// `<pre><code>` blocks, and the answer to the example is the last `<code><em>` in each part's
+/// article.
+#[derive(Debug, Default, PartialEq)]
+pub struct PuzzlePage {
+    pub title: Option<String>,
+    pub examples: Vec<String>,
+    /// The example answer for each part that has one, in part order.
+    pub answers: Vec<(Part, String)>,
+}
+
+impl PuzzlePage {
+    pub fn parse(html: &str) -> PuzzlePage {
+        let title = between(html, "<h2>", "</h2>").map(|heading| {
+            let heading = text(heading);
+            let heading = heading.trim_matches(|c| c == '-' || c == ' ');
+            // "Day 1: Calorie Counting"
+            match heading.split_once(": ") {
+                Some((_, title)) => title.to_string(),
+                None => heading.to_string(),
+            }
+        });
+
+        let mut examples = Vec::new();
+        let mut answers = Vec::new();
+
+        let articles = html.split("<article class=\"day-desc\">").skip(1);
+        for (article, part) in articles.zip(Part::all()) {
+            let article = article.split("</article>").next().unwrap_or_default();
+
+            examples.extend(
+                all_between(article, "<pre><code>", "</code></pre>").map(|(_, block)| text(block)),
+            );
+
+            let answer = all_between(article, "<code><em>", "</em></code>")
+                .chain(all_between(article, "<em><code>", "</code></em>"))
+                .max_by_key(|(position, _)| *position);
+            if let Some((_, answer)) = answer {
+                answers.push((*part, text(answer)));
+            }
+        }
+
+        PuzzlePage {
+            title,
+            examples,
+            answers,
+        }
+    }
+
+    /// The file name each example is saved as: `example.txt` for the first and `example-N.txt` for
+    /// the Nth after that.
+    pub fn example_file_name(index: usize) -> String {
+        if index == 0 {
+            "example.txt".to_string()
+        } else {
+            format!("example-{}.txt", index + 1)
+        }
+    }
+
+    /// Write the title and examples into a day's directory, and the examples' answers into its
+    /// expected answers file, reporting what was written. The answers are taken to belong to the
+    /// example numbered `answers_example`, counting from 1, which is usually the first.
+    ///
+    /// Nothing already in the directory is replaced. Differences from it are reported instead.
+    pub fn save(&self, day_dir: &Path, answers_example: usize) -> Result<(), String> {
+        if answers_example == 0
+            || (answers_example > self.examples.len() && !self.answers.is_empty())
+        {
+            return Err(format!(
+                "There is no example {} on the page, which has {}",
+                answers_example,
+                self.examples.len()
+            ));
+        }
+
+        fs::create_dir_all(day_dir)
+            .map_err(|e| format!("Unable to create {}: {}", day_dir.display(), e))?;
+
+        let title = self
+            .title
+            .as_ref()
+            .map(|title| (day_dir.join(report::TITLE_FILENAME), format!("{}\n", title)));
+        let examples = self
+            .examples
+            .iter()
+            .enumerate()
+            .map(|(i, example)| (day_dir.join(Self::example_file_name(i)), example.clone()));
+        for (path, contents) in title.into_iter().chain(examples) {
+            match fs::read_to_string(&path) {
+                Ok(existing) if existing == contents => println!("{}: unchanged", path.display()),
+                Ok(_) => println!("{}: DIFFERENT, not replaced", path.display()),
+                Err(_) => {
+                    fs::write(&path, contents)
+                        .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+                    println!("{}: written", path.display());
+                }
+            }
+        }
+
+        if self.answers.is_empty() {
+            return Ok(());
+        }
+
+        let answers_path = day_dir.join(answers::FILENAME);
+        let mut expected = if answers_path.exists() {
+            ExpectedAnswers::read(&answers_path)?
+        } else {
+            ExpectedAnswers::new()
+        };
+
+        let file = Self::example_file_name(answers_example - 1);
+        for (part, answer) in &self.answers {
+            let answer: Answer = answer.parse().unwrap();
+            match expected.get(&file, *part) {
+                Some(existing) if *existing == answer => (),
+                Some(existing) => {
+                    println!(
+                        "{} part {}: the page says {}, keeping {}",
+                        file, part, answer, existing
+                    );
+                    continue;
+                }
+                None => println!("{} part {}: {}", file, part, answer),
+            }
+            expected.set(&file, *part, answer);
+        }
+
+        fs::write(&answers_path, expected.to_string())
+            .map_err(|e| format!("Unable to write {}: {}", answers_path.display(), e))
+    }
+}
+
+/// The text between the first `start` in `s` and the `end` after it.
+fn between<'a>(s: &'a str, start: &'a str, end: &'a str) -> Option<&'a str> {
+    all_between(s, start, end).next().map(|(_, found)| found)
+}
+
+/// Every piece of text between a `start` and the `end` after it, with where it is in `s`.
+fn all_between<'a>(
+    s: &'a str,
+    start: &'a str,
+    end: &'a str,
+) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let from = offset + s[offset..].find(start)? + start.len();
+        let to = from + s[from..].find(end)?;
+        offset = to + end.len();
+        Some((from, &s[from..to]))
+    })
+}
+
+/// The text of some HTML, without its tags and with its character references decoded.
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::PuzzlePage;
+    use aoc::Part;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example:</p>
+<pre><code>1000
+2000
+
+3000
+</code></pre>
+<p>The first Elf is carrying <code>3000</code> Calories, and <em>one</em> carries more.</p>
+<pre><code>&lt;<em>x</em>&gt; &amp; y
+</code></pre>
+<p>That Elf is carrying <code><em>3000</em></code> Calories.</p>
+</article>
+<p>Your puzzle answer was <code>70374</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Together they carry <em><code>6000</code></em> Calories.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn examples_and_answers_are_found() {
+        let page = PuzzlePage::parse(PAGE);
+        assert!(page.title.as_deref() == Some("Calorie Counting"));
+        assert!(page.examples == vec!["1000\n2000\n\n3000\n", "<x> & y\n"]);
+        assert!(
+            page.answers
+                == vec![
+                    (Part::One, "3000".to_string()),
+                    (Part::Two, "6000".to_string())
+                ]
+        );
+    }
+
+    #[test]
+    fn examples_are_saved_with_their_answers() {
+        let day_dir = std::env::temp_dir().join(format!("aoc-puzzle-test-{}", std::process::id()));
+        let page = PuzzlePage::parse(PAGE);
+        page.save(&day_dir, 1).unwrap();
+
+        assert!(std::fs::read_to_string(day_dir.join("example-2.txt")).unwrap() == "<x> & y\n");
+        assert!(
+            std::fs::read_to_string(day_dir.join("title.txt")).unwrap() == "Calorie Counting\n"
+        );
+        let answers = std::fs::read_to_string(day_dir.join("answers.txt")).unwrap();
+        assert!(answers == "example.txt\n1: 3000\n2: 6000\n");
+
+        // Saving again changes nothing.
+        page.save(&day_dir, 1).unwrap();
+        assert!(std::fs::read_to_string(day_dir.join("answers.txt")).unwrap() == answers);
+        assert!(page.save(&day_dir, 3).is_err());
+
+        std::fs::remove_dir_all(&day_dir).unwrap();
+    }
+}
@@ -0,0 +1,19 @@
+use aoc::Registry;
+
+/// Every day with a solution written in Rust. Day 7 was solved in Python, in `day07.py`.
+pub fn days() -> Registry {
+    Registry::new()
+        .register::<day1::Day1>(1)
+        .register::<day2::Day2>(2)
+        .register::<day3::Day3>(3)
+        .register::<day4::Day4>(4)
+        .register::<day5::Day5>(5)
+        .register::<day6::Day6>(6)
+        .register::<day8::Day8>(8)
+        .register::<day9::Day9>(9)
+        .register::<day10::Day10>(10)
+        .register::<day11::Day11>(11)
+        .register::<day12::Day12>(12)
+        .register::<day13::Day13>(13)
+        .register::<day14::Day14>(14)
+}
@@ -0,0 +1,239 @@
+use crate::answers::{self, ExpectedAnswers};
+use crate::bench::Statistics;
+use crate::isolation::{self, Outcome};
+use crate::summary::format_duration;
+use aoc::{Day, Part};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The name of the file in a day's directory holding the puzzle's title.
+pub const TITLE_FILENAME: &str = "title.txt";
+
+/// The comments the report goes between in the README. Everything between them is replaced each
+/// time the report is made, and everything else is left alone.
+pub const START_MARKER: &str = "<!-- aoc report start -->";
+pub const END_MARKER: &str = "<!-- aoc report end -->";
+
+/// Whether a day's puzzle is solved.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// Both parts gave the expected answers.
+    Solved,
+    /// Both parts gave answers, but at least one isn't in the expected answers file to check.
+    Unverified,
+    /// A part failed or gave a wrong answer, or there's no input.
+    Unsolved,
+}
+
+impl Status {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Status::Solved => "yes",
+            Status::Unverified => "unverified",
+            Status::Unsolved => "no",
+        }
+    }
+}
+
+/// How one day is doing, for a row of the report.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayReport {
+    pub day: u32,
+    pub title: Option<String>,
+    pub status: Status,
+    /// The median time to parse the input and solve both parts.
+    pub median_time: Option<Duration>,
+    /// Where the day's crate is, relative to the README.
+    pub crate_link: String,
+}
+
+impl DayReport {
+    /// Run both parts of a day with its puzzle input, once in isolation to see that they work and
+    /// then `iterations` times to time them.
+    pub fn run(
+        day: &Day,
+        day_dir: &Path,
+        crate_link: &str,
+        iterations: usize,
+        timeout: Duration,
+    ) -> DayReport {
+        let title = fs::read_to_string(day_dir.join(TITLE_FILENAME))
+            .ok()
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty());
+        let mut report = DayReport {
+            day: day.number(),
+            title,
+            status: Status::Unsolved,
+            median_time: None,
+            crate_link: crate_link.to_string(),
+        };
+
+        let Ok(input) = aoc::crypt::read_input(&day_dir.join(aoc::INPUT_FILENAME)) else {
+            return report;
+        };
+        let input: Arc<str> = input.into();
+        let expected = ExpectedAnswers::read(&day_dir.join(answers::FILENAME)).ok();
+
+        let mut status = Status::Solved;
+        for part in Part::all() {
+            let Outcome::Solved(run) =
+                isolation::run_isolated(*day, input.clone().into(), *part, timeout)
+            else {
+                return report;
+            };
+            match expected
+                .as_ref()
+                .and_then(|expected| expected.get(aoc::INPUT_FILENAME, *part))
+            {
+                Some(answer) if *answer != run.answer => return report,
+                Some(_) => (),
+                None => status = Status::Unverified,
+            }
+        }
+        report.status = status;
+
+        let samples: Vec<Duration> = (0..iterations)
+            .filter_map(|_| {
+                Part::all()
+                    .iter()
+                    .map(|part| day.run(&input, *part).ok().map(|run| run.total_time()))
+                    .sum()
+            })
+            .collect();
+        report.median_time = Statistics::from_samples(&samples).map(|s| s.median);
+        report
+    }
+}
+
+/// The report as a Markdown table.
+pub fn table(reports: &[DayReport]) -> String {
+    let mut table = String::from(
+        "| Day | Puzzle | Both parts solved | Median time | Solution |\n\
+         | --: | ------ | :---------------: | ----------: | -------- |\n",
+    );
+    for report in reports {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | [{}]({}) |\n",
+            report.day,
+            report.title.as_deref().unwrap_or(""),
+            report.status.describe(),
+            report.median_time.map(format_duration).unwrap_or_default(),
+            Path::new(&report.crate_link)
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default(),
+            report.crate_link
+        ));
+    }
+    table
+}
+
+/// The README with `table` between the markers, replacing whatever was there. If the markers
+/// aren't there yet, they're added at the end with a heading.
+///
+/// Timings are never quite the same twice, so a table that only differs from the one already there
+/// in its times leaves the README as it is, rather than changing it every time.
+pub fn insert_report(readme: &str, table: &str) -> String {
+    let section = format!("{}\n{}{}", START_MARKER, table, END_MARKER);
+
+    if let Some(start) = readme.find(START_MARKER) {
+        if let Some(end) = readme[start..].find(END_MARKER) {
+            let current = &readme[start + START_MARKER.len()..start + end];
+            if without_times(current.trim_start_matches('\n')) == without_times(table) {
+                return readme.to_string();
+            }
+            let end = start + end + END_MARKER.len();
+            return format!("{}{}{}", &readme[..start], section, &readme[end..]);
+        }
+    }
+
+    let mut readme = readme.to_string();
+    if !readme.is_empty() && !readme.ends_with('\n') {
+        readme.push('\n');
+    }
+    format!("{}\n## Results\n\n{}\n", readme, section)
+}
+
+/// A table made by [table] with the median times taken out of its rows. The headings are kept.
+fn without_times(table: &str) -> Vec<Vec<&str>> {
+    const HEADING_LINES: usize = 2;
+    const TIME_COLUMN: usize = 3;
+
+    table
+        .lines()
+        .enumerate()
+        .map(|(line_number, line)| {
+            line.split('|')
+                .map(str::trim)
+                .enumerate()
+                // The first cell is what's before the first `|`.
+                .filter(|(i, _)| line_number < HEADING_LINES || *i != TIME_COLUMN + 1)
+                .map(|(_, cell)| cell)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{insert_report, table, DayReport, Status};
+    use std::time::Duration;
+
+    fn reports() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: 1,
+                title: Some("Calorie Counting".to_string()),
+                status: Status::Solved,
+                median_time: Some(Duration::from_micros(150)),
+                crate_link: "2022/day01".to_string(),
+            },
+            DayReport {
+                day: 14,
+                title: None,
+                status: Status::Unsolved,
+                median_time: None,
+                crate_link: "2022/day14".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn reports_are_tables() {
+        let table = table(&reports());
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines.len() == 4);
+        assert!(lines[2] == "| 1 | Calorie Counting | yes | 0.150 ms | [day01](2022/day01) |");
+        assert!(lines[3] == "| 14 |  | no |  | [day14](2022/day14) |");
+    }
+
+    #[test]
+    fn reports_replace_the_last_one() {
+        let readme = "Advent of Code\n==============";
+        let first = insert_report(readme, "| old |\n");
+        assert!(first.starts_with("Advent of Code\n==============\n\n## Results\n\n<!--"));
+        assert!(first.ends_with("| old |\n<!-- aoc report end -->\n"));
+
+        let edited = format!("{}\nMore notes.\n", first);
+        let second = insert_report(&edited, "| new |\n");
+        assert!(second == edited.replace("| old |", "| new |"));
+        assert!(insert_report(&second, "| new |\n") == second);
+    }
+
+    #[test]
+    fn only_new_timings_leave_the_report_alone() {
+        let mut reports = reports();
+        let readme = insert_report("", &table(&reports));
+
+        reports[0].median_time = Some(Duration::from_micros(170));
+        assert!(insert_report(&readme, &table(&reports)) == readme);
+
+        reports[1].status = Status::Unverified;
+        let updated = insert_report(&readme, &table(&reports));
+        assert!(updated.contains("| 1 | Calorie Counting | yes | 0.170 ms |"));
+        assert!(updated.contains("| 14 |  | unverified |  |"));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::answers::{self, ExpectedAnswers};
+use aoc::Part;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The directory holding the day crates and this one.
+pub fn default_crates_dir() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+const CARGO_TOML: &str = r#"[package]
+name = "dayN"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc = { path = "../aoc" }
+"#;
+
+const GEOMETRY_DEPENDENCY: &str = "geometry = { path = \"../geometry\" }\n";
+
+const MAIN_RS: &str = r#"fn main() {
+    aoc::main::<dayN::DayN>(N);
+}
+"#;
+
+const LIB_RS: &str = r#"use aoc::{ParseError, Solution};
+
+pub struct DayN;
+
+impl Solution for DayN {
+    type Input = Vec<String>;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        aoc::parse_lines(input, |line| Ok(line.to_string()))
+    }
+
+    /// Not solved yet, so there's no answer.
+    fn part1(_input: &Self::Input) -> Option<usize> {
+        None
+    }
+
+    /// Not solved yet, so there's no answer.
+    fn part2(_input: &Self::Input) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::DayN;
+    use aoc::Solution;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+TESTS}
+"#;
+
+/// The test of one part's answer to the example.
+const PART_TEST: &str = r#"    #[test]
+    fn example_part_P() {
+        let input = DayN::parse(EXAMPLE).unwrap();
+        let expected: aoc::Answer = ANSWER.parse().unwrap();
+        assert!(aoc::Answer::from(DayN::partP(&input)) == expected);
+    }
+"#;
+
+/// The test when there's no answer to the example yet.
+const PARSE_TEST: &str = r#"    #[test]
+    fn example_parses() {
+        assert!(DayN::parse(EXAMPLE).is_ok());
+    }
+"#;
+
+/// Whether a crate's directory holds nothing but what `cargo new` made, which can be replaced.
+fn is_cargo_new_stub(day_dir: &Path) -> bool {
+    let main_rs = fs::read_to_string(day_dir.join("src").join("main.rs")).unwrap_or_default();
+    !day_dir.join("src").join("lib.rs").exists() && main_rs.contains("Hello, world!")
+}
+
+/// Make a crate for day `day` in `crates_dir`, with parsing and part stubs and a test for the
+/// example's answers, and register it with the runner. Returns the files that were written.
+///
+/// The example is `example.txt` in the day's directory, which is left alone if it's already there,
+/// and its answers come from the day's expected answers file if it has them. A day that already
+/// has a crate isn't touched, unless it's just the `Hello, world!` that `cargo new` makes.
+pub fn new_day(crates_dir: &Path, day: u32, geometry: bool) -> Result<Vec<PathBuf>, String> {
+    let day_dir = crates_dir.join(format!("day{:02}", day));
+    if day_dir.join("Cargo.toml").exists() && !is_cargo_new_stub(&day_dir) {
+        return Err(format!("{} already has a crate", day_dir.display()));
+    }
+
+    let fill_in = |template: &str| {
+        template
+            .replace("dayN", &format!("day{}", day))
+            .replace("DayN", &format!("Day{}", day))
+            .replace("(N)", &format!("({})", day))
+    };
+
+    let mut cargo_toml = fill_in(CARGO_TOML);
+    if geometry {
+        cargo_toml.push_str(GEOMETRY_DEPENDENCY);
+    }
+
+    let answers_path = day_dir.join(answers::FILENAME);
+    let expected = if answers_path.exists() {
+        ExpectedAnswers::read(&answers_path)?
+    } else {
+        ExpectedAnswers::new()
+    };
+    let mut tests = Vec::new();
+    for part in Part::all() {
+        if let Some(answer) = expected.get("example.txt", *part) {
+            tests.push(
+                PART_TEST
+                    .replace("partP", &format!("part{}", part))
+                    .replace("part_P", &format!("part_{}", part))
+                    .replace("ANSWER", &format!("{:?}", answer.to_string())),
+            );
+        }
+    }
+    if tests.is_empty() {
+        tests.push(PARSE_TEST.to_string());
+    }
+    let lib_rs = fill_in(&LIB_RS.replace("TESTS", &tests.join("\n")));
+
+    let mut files = vec![
+        (day_dir.join("Cargo.toml"), cargo_toml),
+        (day_dir.join("src").join("main.rs"), fill_in(MAIN_RS)),
+        (day_dir.join("src").join("lib.rs"), lib_rs),
+    ];
+    if !day_dir.join("example.txt").exists() {
+        files.push((day_dir.join("example.txt"), String::new()));
+    }
+
+    let runner_dir = crates_dir.join("aoc2022");
+    files.push(register_dependency(&runner_dir, day)?);
+    files.push(register_day(&runner_dir, day)?);
+
+    fs::create_dir_all(day_dir.join("src"))
+        .map_err(|e| format!("Unable to create {}: {}", day_dir.display(), e))?;
+    for (path, contents) in &files {
+        fs::write(path, contents)
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// The runner's `Cargo.toml` with a dependency on the day's crate, after the days before it.
+fn register_dependency(runner_dir: &Path, day: u32) -> Result<(PathBuf, String), String> {
+    let path = runner_dir.join("Cargo.toml");
+    let manifest = fs::read_to_string(&path)
+        .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+    let line = format!("day{} = {{ path = \"../day{:02}\" }}", day, day);
+
+    let lines = insert_in_order(&manifest, &line, "day", " =", day)
+        .ok_or_else(|| format!("There are no day dependencies in {}", path.display()))?;
+    Ok((path, lines))
+}
+
+/// The runner's registry with the day registered, after the days before it.
+fn register_day(runner_dir: &Path, day: u32) -> Result<(PathBuf, String), String> {
+    let path = runner_dir.join("src").join("registry.rs");
+    let registry = fs::read_to_string(&path)
+        .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+    let line = format!("        .register::<day{}::Day{}>({})", day, day, day);
+
+    let lines = insert_in_order(&registry, &line, ".register::<day", "::", day)
+        .ok_or_else(|| format!("There are no registered days in {}", path.display()))?;
+    Ok((path, lines))
+}
+
+/// Insert `new_line` into `text` after the last line that's for a day before `day`. Lines for days
+/// look like `{prefix}{day}{suffix}...` once trimmed. Returns `None` if there aren't any.
+fn insert_in_order(
+    text: &str,
+    new_line: &str,
+    prefix: &str,
+    suffix: &str,
+    day: u32,
+) -> Option<String> {
+    let day_of = |line: &str| -> Option<u32> {
+        let rest = line.trim().strip_prefix(prefix)?;
+        let (number, _) = rest.split_once(suffix)?;
+        number.parse().ok()
+    };
+
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.iter().any(|line| day_of(line) == Some(day)) {
+        return Some(text.to_string());
+    }
+
+    let day_lines: Vec<usize> = (0..lines.len())
+        .filter(|&i| day_of(lines[i]).is_some())
+        .collect();
+    let first = *day_lines.first()?;
+    let position = day_lines
+        .iter()
+        .rfind(|&&i| day_of(lines[i]) < Some(day))
+        .map(|i| i + 1)
+        .unwrap_or(first);
+
+    lines.insert(position, new_line);
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Some(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{insert_in_order, new_day};
+    use std::fs;
+
+    #[test]
+    fn days_are_inserted_in_order() {
+        let registry =
+            "Registry::new()\n    .register::<day1::Day1>(1)\n    .register::<day9::Day9>(9)\n";
+        let inserted = insert_in_order(
+            registry,
+            "    .register::<day3::Day3>(3)",
+            ".register::<day",
+            "::",
+            3,
+        );
+        assert!(
+            inserted.as_deref()
+                == Some("Registry::new()\n    .register::<day1::Day1>(1)\n    .register::<day3::Day3>(3)\n    .register::<day9::Day9>(9)\n")
+        );
+
+        // Registering again changes nothing.
+        let again = insert_in_order(
+            registry,
+            "    .register::<day9::Day9>(9)",
+            ".register::<day",
+            "::",
+            9,
+        );
+        assert!(again.as_deref() == Some(registry));
+        assert!(insert_in_order("", "x", "day", " =", 1).is_none());
+    }
+
+    #[test]
+    fn a_new_day_is_made_and_registered() {
+        let crates_dir = std::env::temp_dir().join(format!("aoc-new-test-{}", std::process::id()));
+        let runner_dir = crates_dir.join("aoc2022");
+        fs::create_dir_all(runner_dir.join("src")).unwrap();
+        fs::write(
+            runner_dir.join("Cargo.toml"),
+            "[dependencies]\naoc = { path = \"../aoc\" }\nday1 = { path = \"../day01\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            runner_dir.join("src").join("registry.rs"),
+            "    Registry::new()\n        .register::<day1::Day1>(1)\n}\n",
+        )
+        .unwrap();
+        let day_dir = crates_dir.join("day14");
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::write(
+            day_dir.join("answers.txt"),
+            "example.txt\n1: 24\n2:\n#.\n.#\n",
+        )
+        .unwrap();
+
+        new_day(&crates_dir, 14, true).unwrap();
+
+        let lib_rs = fs::read_to_string(day_dir.join("src").join("lib.rs")).unwrap();
+        assert!(lib_rs.contains("pub struct Day14;"));
+        assert!(lib_rs.contains("let expected: aoc::Answer = \"24\".parse().unwrap();"));
+        assert!(lib_rs.contains(r##"let expected: aoc::Answer = "#.\n.#".parse().unwrap();"##));
+        assert!(lib_rs.contains("assert!(aoc::Answer::from(Day14::part2(&input)) == expected);"));
+        let main_rs = fs::read_to_string(day_dir.join("src").join("main.rs")).unwrap();
+        assert!(main_rs.contains("aoc::main::<day14::Day14>(14);"));
+        let cargo_toml = fs::read_to_string(day_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"day14\"") && cargo_toml.contains("geometry"));
+        let manifest = fs::read_to_string(runner_dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.ends_with("day14 = { path = \"../day14\" }\n"));
+        let registry = fs::read_to_string(runner_dir.join("src").join("registry.rs")).unwrap();
+        assert!(registry
+            .contains(".register::<day1::Day1>(1)\n        .register::<day14::Day14>(14)\n}"));
+
+        // The crate is there now, so it isn't made again.
+        assert!(new_day(&crates_dir, 14, false).is_err());
+
+        fs::remove_dir_all(&crates_dir).unwrap();
+    }
+}
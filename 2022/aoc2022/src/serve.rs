@@ -0,0 +1,305 @@
+use crate::isolation::{self, Outcome};
+use aoc::{Part, Registry};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::num::NonZeroUsize;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The port the server listens on when no other is given.
+pub const DEFAULT_PORT: u16 = 2022;
+
+/// The year of the puzzles solved here.
+const YEAR: &str = "2022";
+
+/// The largest input accepted. The biggest real inputs are tens of kilobytes.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The most the request line and headers together can take up.
+const MAX_HEADER_SIZE: u64 = 16 * 1024;
+
+/// How many connections can wait for a worker before new ones wait to be accepted.
+const QUEUE_LENGTH: usize = 16;
+
+/// How long a client can take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A response's status code and JSON body.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", aoc::json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            431 => "Request Header Fields Too Large",
+            422 => "Unprocessable Entity",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Answer requests to solve puzzles until the listener fails, with a worker thread for each CPU.
+/// Each part is solved in isolation, so one that runs too long is stopped after `timeout`.
+///
+/// `POST /2022/<day>/<part>` with the puzzle input as the body solves a part, and the response is
+/// the answer and its timings as JSON, like `--format json` prints. Anything else gets an error
+/// status with an `{"error": "..."}` body.
+pub fn serve(listener: TcpListener, days: Arc<Registry>, timeout: Duration) -> io::Result<()> {
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(QUEUE_LENGTH);
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    for i in 0..workers {
+        let receiver = receiver.clone();
+        let days = days.clone();
+        thread::Builder::new()
+            .name(format!("worker {}", i))
+            .spawn(move || loop {
+                // The lock is only held while waiting for a connection, not while answering it.
+                let Ok(stream) = receiver.lock().unwrap().recv() else {
+                    return;
+                };
+                if let Err(e) = handle(stream, &days, timeout) {
+                    aoc::debug!("Unable to answer a request: {}", e);
+                }
+            })?;
+    }
+
+    for stream in listener.incoming() {
+        // Blocks while the queue is full, so connections wait in the listener's backlog.
+        if sender.send(stream?).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream, days: &Registry, timeout: Duration) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = match read_request(&stream) {
+        Ok((method, path, body)) => respond(&method, &path, &body, days, timeout),
+        Err(response) => response,
+    };
+    aoc::debug!("{} {}", response.status, response.body);
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// The method, path and body of a request.
+fn read_request(stream: &TcpStream) -> Result<(String, String, Vec<u8>), Response> {
+    let mut reader = BufReader::new(stream);
+    let mut header_size_left = MAX_HEADER_SIZE;
+
+    let request_line = read_header_line(&mut reader, &mut header_size_left)?;
+    let mut words = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Err(Response::error(400, "The request line is malformed"));
+    };
+
+    let mut content_length = 0;
+    loop {
+        let header = read_header_line(&mut reader, &mut header_size_left)?;
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| Response::error(400, "Content-Length isn't a number"))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(Response::error(413, "The input is too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(400, "The request couldn't be read"))?;
+
+    Ok((method.to_string(), path.to_string(), body))
+}
+
+/// Read the request line or a header, as long as it fits in what's `left` of the room for them.
+fn read_header_line<R: BufRead>(reader: &mut R, left: &mut u64) -> Result<String, Response> {
+    let mut line = String::new();
+    let length = reader
+        .take(*left)
+        .read_line(&mut line)
+        .map_err(|_| Response::error(400, "The request couldn't be read"))?;
+    *left -= length as u64;
+
+    if !line.ends_with('\n') {
+        return Err(if *left == 0 {
+            Response::error(431, "The request headers are too large")
+        } else {
+            Response::error(400, "The request ended in its headers")
+        });
+    }
+    Ok(line)
+}
+
+/// The response to a request to solve a part of a puzzle.
+pub fn respond(
+    method: &str,
+    path: &str,
+    body: &[u8],
+    days: &Registry,
+    timeout: Duration,
+) -> Response {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let [year, day, part] = segments[..] else {
+        return Response::error(404, "Expected a path like /2022/<day>/<part>");
+    };
+    if year != YEAR {
+        return Response::error(404, &format!("Only {} puzzles are solved here", YEAR));
+    }
+    let Some(day) = day.parse().ok().and_then(|number| days.get(number)) else {
+        return Response::error(404, &format!("There's no solution for day {}", day));
+    };
+    let Some(part) = part.parse().ok().and_then(Part::from_number) else {
+        return Response::error(404, &format!("There's no part {}, only 1 and 2", part));
+    };
+    if method != "POST" {
+        return Response::error(405, "POST the puzzle input to solve it");
+    }
+    let Ok(input) = std::str::from_utf8(body) else {
+        return Response::error(400, "The input isn't UTF-8 text");
+    };
+
+    match isolation::run_isolated(*day, input.into(), part, timeout) {
+        Outcome::Solved(run) => Response {
+            status: 200,
+            body: aoc::run_to_json(day.number(), part, &run),
+        },
+        Outcome::ParseError(error) => Response::error(422, &error.to_string()),
+        outcome => Response::error(500, &outcome.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_header_line, respond, serve, MAX_HEADER_SIZE};
+    use aoc::{ParseError, Registry, Solution};
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Part 1 adds up the numbers, and part 2 panics.
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            aoc::parse_lines(input, |line| aoc::parse_number(line, line))
+        }
+
+        fn part1(input: &Self::Input) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(_input: &Self::Input) -> u32 {
+            panic!("Not solved yet")
+        }
+    }
+
+    fn days() -> Registry {
+        Registry::new().register::<Sum>(1)
+    }
+
+    #[test]
+    fn requests_are_answered() {
+        let timeout = Duration::from_secs(5);
+        let post =
+            |path: &str, body: &str| respond("POST", path, body.as_bytes(), &days(), timeout);
+
+        let solved = post("/2022/1/1", "1\n2\n");
+        assert!(solved.status == 200);
+        assert!(solved
+            .body
+            .starts_with("{\"day\":1,\"part\":1,\"answer\":3,\"parse_time_ns\":"));
+
+        assert!(post("/2022/7/1", "").status == 404);
+        assert!(post("/2022/7/1", "").body == "{\"error\":\"There's no solution for day 7\"}");
+        assert!(post("/2021/1/1", "").status == 404);
+        assert!(post("/2022/1/3", "").status == 404);
+        assert!(post("/2022/1", "").status == 404);
+        assert!(post("/2022/1/1", "x").status == 422);
+        assert!(post("/2022/1/2", "1").status == 500);
+        assert!(respond("GET", "/2022/1/1", b"", &days(), timeout).status == 405);
+        assert!(respond("POST", "/2022/1/1", &[0xff], &days(), timeout).status == 400);
+    }
+
+    #[test]
+    fn headers_are_limited() {
+        let mut left = MAX_HEADER_SIZE;
+        let mut request: &[u8] = b"POST / HTTP/1.1\r\nHost: localhost\r\n";
+        assert!(read_header_line(&mut request, &mut left).unwrap() == "POST / HTTP/1.1\r\n");
+        assert!(read_header_line(&mut request, &mut left).unwrap() == "Host: localhost\r\n");
+        assert!(left == MAX_HEADER_SIZE - 34);
+        assert!(
+            read_header_line(&mut request, &mut left)
+                .unwrap_err()
+                .status
+                == 400
+        );
+
+        let long = format!("X-Padding: {}\r\n", "a".repeat(MAX_HEADER_SIZE as usize));
+        let mut left = MAX_HEADER_SIZE;
+        let error = read_header_line(&mut long.as_bytes(), &mut left).unwrap_err();
+        assert!(error.status == 431);
+    }
+
+    #[test]
+    fn answers_are_served_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, Arc::new(days()), Duration::from_secs(5)));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST /2022/1/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\n10\n20"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json\r\n"));
+        assert!(response.contains("\r\n\r\n{\"day\":1,\"part\":1,\"answer\":30,"));
+    }
+}
@@ -0,0 +1,376 @@
+use aoc::{Answer, Part, Run};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The name of the answer store in the data directory.
+pub const FILENAME: &str = "answer-store.tsv";
+
+const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const PRIME: u64 = 0x100000001b3;
+
+/// A hash of a puzzle input, which is the same for the same input on any machine and any version
+/// of Rust. This is 64-bit FNV-1a, written in hex.
+pub fn input_hash(input: &str) -> String {
+    format!("{:016x}", add_to_hash(OFFSET_BASIS, input.as_bytes()))
+}
+
+/// The [input_hash] of everything read from `reader`, read a buffer at a time.
+pub fn read_input_hash(mut reader: impl BufRead) -> io::Result<String> {
+    let mut hash = OFFSET_BASIS;
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(format!("{:016x}", hash));
+        }
+        hash = add_to_hash(hash, buffer);
+        let length = buffer.len();
+        reader.consume(length);
+    }
+}
+
+fn add_to_hash(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+/// The git revision of the working tree, marked `-dirty` if there are uncommitted changes, or
+/// `unknown` if git can't say.
+pub fn git_revision() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|revision| revision.trim().to_string())
+        .filter(|revision| !revision.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// An answer that was worked out, and when and how quickly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub input_hash: String,
+    pub answer: Answer,
+    pub revision: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Seconds since the Unix epoch.
+    pub recorded_at: u64,
+}
+
+impl Record {
+    fn is_for(&self, day: u32, part: Part, input_hash: &str) -> bool {
+        self.day == day && self.part == part && self.input_hash == input_hash
+    }
+
+    /// The record as a run, for printing in place of running again.
+    pub fn to_run(&self) -> Run {
+        Run {
+            answer: self.answer.clone(),
+            parse_time: self.parse_time,
+            solve_time: self.solve_time,
+            parse_memory: None,
+            solve_memory: None,
+        }
+    }
+
+    /// The record as a line of the store. Newlines in answers, like day 10's letters, are written
+    /// as `\n` so each record stays on one line.
+    fn to_line(&self) -> String {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            self.input_hash.clone(),
+            self.answer
+                .to_string()
+                .replace('\\', "\\\\")
+                .replace('\n', "\\n"),
+            self.revision.clone(),
+            self.parse_time.as_nanos().to_string(),
+            self.solve_time.as_nanos().to_string(),
+            self.recorded_at.to_string(),
+        ]
+        .join("\t")
+    }
+
+    fn from_line(line: &str) -> Option<Record> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [day, part, input_hash, answer, revision, parse_time, solve_time, recorded_at] =
+            fields[..]
+        else {
+            return None;
+        };
+
+        Some(Record {
+            day: day.parse().ok()?,
+            part: Part::from_number(part.parse().ok()?)?,
+            input_hash: input_hash.to_string(),
+            answer: unescape(answer).parse().unwrap(),
+            revision: revision.to_string(),
+            parse_time: Duration::from_nanos(parse_time.parse().ok()?),
+            solve_time: Duration::from_nanos(solve_time.parse().ok()?),
+            recorded_at: recorded_at.parse().ok()?,
+        })
+    }
+}
+
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// An answer that's different from the one recorded before for the same input.
+#[derive(Debug)]
+pub struct Regression {
+    pub previous: Record,
+    pub answer: Answer,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let previous = &self.previous;
+        write!(
+            f,
+            "REGRESSION: day {} part {} answered {} for input {}, which was {} at revision {}",
+            previous.day,
+            previous.part,
+            self.answer,
+            previous.input_hash,
+            previous.answer,
+            previous.revision
+        )
+    }
+}
+
+/// Every answer the runner has worked out, keyed by day, part and a hash of the input.
+///
+/// The store is a file with a tab-separated [Record] on each line, which is only ever added to.
+/// The latest record for a key is the answer that counts, and later answers are checked against it.
+/// A different answer is a regression, which is only added when it's accepted, like after fixing a
+/// wrong answer, so the store keeps each answer a part has been accepted with.
+pub struct AnswerStore {
+    path: PathBuf,
+    records: Vec<Record>,
+}
+
+impl AnswerStore {
+    /// Open the store at `path`. It's fine for there not to be one yet.
+    pub fn open(path: &Path) -> Result<AnswerStore, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Unable to read {}: {}", path.display(), e)),
+        };
+
+        let records = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                Record::from_line(line)
+                    .ok_or_else(|| format!("{}: line {} isn't a record", path.display(), i + 1))
+            })
+            .collect::<Result<Vec<Record>, String>>()?;
+
+        Ok(AnswerStore {
+            path: path.to_path_buf(),
+            records,
+        })
+    }
+
+    /// The latest answer for a part of a day with an input, which later answers are checked
+    /// against.
+    pub fn get(&self, day: u32, part: Part, input_hash: &str) -> Option<&Record> {
+        self.records
+            .iter()
+            .rev()
+            .find(|r| r.is_for(day, part, input_hash))
+    }
+
+    /// Add the answer of a run to the store, unless it's already the answer for the same input.
+    /// If the answer for the input was different, that's a regression, which is returned, unless
+    /// `accept` is set, when the new answer is added and becomes the one that counts.
+    pub fn record(
+        &mut self,
+        day: u32,
+        part: Part,
+        input_hash: &str,
+        run: &Run,
+        revision: &str,
+        accept: bool,
+    ) -> Result<Option<Regression>, String> {
+        if let Some(previous) = self.get(day, part, input_hash) {
+            if previous.answer == run.answer {
+                return Ok(None);
+            }
+            if !accept {
+                return Ok(Some(Regression {
+                    previous: previous.clone(),
+                    answer: run.answer.clone(),
+                }));
+            }
+        }
+
+        let record = Record {
+            day,
+            part,
+            input_hash: input_hash.to_string(),
+            answer: run.answer.clone(),
+            revision: revision.to_string(),
+            parse_time: run.parse_time,
+            solve_time: run.solve_time,
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Unable to open {}: {}", self.path.display(), e))?;
+        writeln!(file, "{}", record.to_line())
+            .map_err(|e| format!("Unable to write {}: {}", self.path.display(), e))?;
+        self.records.push(record);
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{input_hash, read_input_hash, AnswerStore};
+    use aoc::{Answer, Part, Run};
+    use std::fs;
+    use std::time::Duration;
+
+    fn run(answer: Answer) -> Run {
+        Run {
+            answer,
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_nanos(20),
+            parse_memory: None,
+            solve_memory: None,
+        }
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        assert!(input_hash("") == "cbf29ce484222325");
+        assert!(input_hash("a") == "af63dc4c8601ec8c");
+        assert!(input_hash("1000\n") != input_hash("1000"));
+        let long = "1000\n".repeat(2000);
+        assert!(
+            read_input_hash(std::io::BufReader::with_capacity(7, long.as_bytes())).unwrap()
+                == input_hash(&long)
+        );
+    }
+
+    #[test]
+    fn answers_are_kept_and_regressions_found() {
+        let path = std::env::temp_dir().join(format!("aoc-store-test-{}.tsv", std::process::id()));
+        let hash = input_hash("input");
+
+        let mut store = AnswerStore::open(&path).unwrap();
+        assert!(store.get(10, Part::Two, &hash).is_none());
+        let glyphs = run(Answer::glyphs("#.\\\n.#"));
+        assert!(store
+            .record(10, Part::Two, &hash, &glyphs, "abc123", false)
+            .unwrap()
+            .is_none());
+        assert!(store
+            .record(10, Part::Two, &hash, &glyphs, "abc124", false)
+            .unwrap()
+            .is_none());
+        assert!(store
+            .record(10, Part::One, &hash, &run(Answer::from(1)), "abc124", false)
+            .unwrap()
+            .is_none());
+
+        // The store is read back the same.
+        let mut store = AnswerStore::open(&path).unwrap();
+        let record = store.get(10, Part::Two, &hash).unwrap();
+        assert!(record.answer == Answer::glyphs("#.\\\n.#"));
+        assert!(record.revision == "abc123");
+        assert!(record.to_run().solve_time == Duration::from_nanos(20));
+        assert!(store.get(10, Part::Two, &input_hash("other")).is_none());
+
+        let regression = store
+            .record(10, Part::One, &hash, &run(Answer::from(2)), "def456", false)
+            .unwrap()
+            .unwrap();
+        assert!(regression.previous.answer == Answer::from(1));
+        assert!(regression
+            .to_string()
+            .starts_with("REGRESSION: day 10 part 1 answered 2"));
+
+        // Regressions aren't added, so they're found every time, and the stored answer still
+        // counts.
+        assert!(store
+            .record(10, Part::One, &hash, &run(Answer::from(2)), "def457", false)
+            .unwrap()
+            .is_some());
+        assert!(store.get(10, Part::One, &hash).unwrap().answer == Answer::from(1));
+        assert!(fs::read_to_string(&path).unwrap().lines().count() == 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn corrected_answers_can_be_accepted() {
+        let path = std::env::temp_dir().join(format!("aoc-accept-test-{}.tsv", std::process::id()));
+        let hash = input_hash("input");
+        let mut store = AnswerStore::open(&path).unwrap();
+        let record = |store: &mut AnswerStore, answer: u32, accept: bool| {
+            store
+                .record(
+                    5,
+                    Part::One,
+                    &hash,
+                    &run(Answer::from(answer)),
+                    "abc123",
+                    accept,
+                )
+                .unwrap()
+        };
+
+        assert!(record(&mut store, 41, false).is_none());
+        assert!(record(&mut store, 42, false).is_some());
+
+        // Once the corrected answer is accepted it's the one that counts, and the wrong one is a
+        // regression instead.
+        assert!(record(&mut store, 42, true).is_none());
+        assert!(record(&mut store, 42, false).is_none());
+        assert!(record(&mut store, 41, false).is_some());
+
+        let store = AnswerStore::open(&path).unwrap();
+        assert!(store.get(5, Part::One, &hash).unwrap().answer == Answer::from(42));
+        assert!(fs::read_to_string(&path).unwrap().lines().count() == 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+use crate::table::{Align, Table};
+use aoc::{Part, Run};
+use std::fmt;
+use std::time::Duration;
+
+struct Row {
+    day: u32,
+    part: Part,
+    /// The run, or why there isn't one.
+    result: Result<Run, String>,
+}
+
+/// A table of the answers and timings of every part that was run.
+#[derive(Default)]
+pub struct Summary {
+    rows: Vec<Row>,
+}
+
+impl Summary {
+    pub fn new() -> Summary {
+        Summary { rows: Vec::new() }
+    }
+
+    pub fn add(&mut self, day: u32, part: Part, run: Run) {
+        self.rows.push(Row {
+            day,
+            part,
+            result: Ok(run),
+        });
+    }
+
+    /// Add a part that didn't get an answer, with a few words about why.
+    pub fn add_failure(&mut self, day: u32, part: Part, failure: &str) {
+        self.rows.push(Row {
+            day,
+            part,
+            result: Err(failure.to_uppercase()),
+        });
+    }
+
+    /// Add every part of a day that couldn't be run at all, like one with no input.
+    pub fn add_day_failure(&mut self, day: u32, failure: &str) {
+        for part in Part::all() {
+            self.add_failure(day, *part, failure);
+        }
+    }
+
+    pub fn failures(&self) -> usize {
+        self.rows.iter().filter(|r| r.result.is_err()).count()
+    }
+
+    fn runs(&self) -> impl Iterator<Item = &Run> {
+        self.rows.iter().filter_map(|r| r.result.as_ref().ok())
+    }
+
+    pub fn total_parse_time(&self) -> Duration {
+        self.runs().map(|run| run.parse_time).sum()
+    }
+
+    pub fn total_solve_time(&self) -> Duration {
+        self.runs().map(|run| run.solve_time).sum()
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.total_parse_time() + self.total_solve_time()
+    }
+}
+
+/// A duration in milliseconds, to the nearest microsecond.
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut table = Table::new(&[
+            ("Day", Align::Right),
+            ("Part", Align::Right),
+            ("Answer", Align::Left),
+            ("Parse", Align::Right),
+            ("Solve", Align::Right),
+        ]);
+
+        // Answers that span several lines, like the letters drawn on day 10's CRT, get a line of
+        // the table for each line of the answer.
+        for row in &self.rows {
+            let (answer, parse_time, solve_time) = match &row.result {
+                Ok(run) => (
+                    run.answer.to_string(),
+                    format_duration(run.parse_time),
+                    format_duration(run.solve_time),
+                ),
+                Err(failure) => (failure.clone(), String::new(), String::new()),
+            };
+            let mut answer_lines = answer.lines();
+            table.add_row(vec![
+                row.day.to_string(),
+                row.part.to_string(),
+                answer_lines.next().unwrap_or_default().to_string(),
+                parse_time,
+                solve_time,
+            ]);
+            for answer_line in answer_lines {
+                table.add_row(vec![String::new(), String::new(), answer_line.to_string()]);
+            }
+        }
+
+        table.add_rule();
+        table.add_row(vec![
+            "Total".to_string(),
+            String::new(),
+            String::new(),
+            format_duration(self.total_parse_time()),
+            format_duration(self.total_solve_time()),
+        ]);
+
+        write!(f, "{}", table)?;
+        write!(f, "Grand total: {}", format_duration(self.total_time()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Summary;
+    use aoc::{Part, Run};
+    use std::time::Duration;
+
+    fn run(answer: &str, parse_millis: u64, solve_millis: u64) -> Run {
+        Run {
+            answer: answer.parse().unwrap(),
+            parse_time: Duration::from_millis(parse_millis),
+            solve_time: Duration::from_millis(solve_millis),
+            parse_memory: None,
+            solve_memory: None,
+        }
+    }
+
+    #[test]
+    fn totals_sum_every_row() {
+        let mut summary = Summary::new();
+        summary.add(1, Part::One, run("24000", 1, 2));
+        summary.add(1, Part::Two, run("45000", 3, 4));
+
+        assert!(summary.total_parse_time() == Duration::from_millis(4));
+        assert!(summary.total_solve_time() == Duration::from_millis(6));
+        assert!(summary.total_time() == Duration::from_millis(10));
+    }
+
+    #[test]
+    fn columns_are_aligned() {
+        let mut summary = Summary::new();
+        summary.add(1, Part::One, run("24000", 1, 2));
+        summary.add(10, Part::Two, run("##..\n..##", 3, 4));
+        summary.add_failure(11, Part::Two, "timed out");
+        summary.add_day_failure(12, "no input");
+
+        let table = summary.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert!(lines[0] == "  Day  Part  Answer        Parse     Solve");
+        assert!(lines[2] == "    1     1  24000      1.000 ms  2.000 ms");
+        assert!(lines[3] == "   10     2  ##..       3.000 ms  4.000 ms");
+        assert!(lines[4] == "             ..##");
+        assert!(lines[5] == "   11     2  TIMED OUT");
+        assert!(lines[6] == "   12     1  NO INPUT");
+        assert!(lines[7] == "   12     2  NO INPUT");
+        assert!(lines[9] == "Total                   4.000 ms  6.000 ms");
+        assert!(lines[10] == "Grand total: 10.000 ms");
+        assert!(summary.failures() == 3);
+    }
+}
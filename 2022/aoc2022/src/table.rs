@@ -0,0 +1,113 @@
+use std::fmt;
+
+/// Which side of its column a cell is lined up against.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+enum Row {
+    Cells(Vec<String>),
+    Rule,
+}
+
+/// A table of text for the terminal, with headings and a rule under them. Columns are as wide as
+/// their widest cell and two spaces apart, and lines have no trailing spaces.
+pub struct Table {
+    headings: Vec<&'static str>,
+    alignments: Vec<Align>,
+    rows: Vec<Row>,
+}
+
+impl Table {
+    /// A table with a column for each heading, lined up as given.
+    pub fn new(columns: &[(&'static str, Align)]) -> Table {
+        Table {
+            headings: columns.iter().map(|(heading, _)| *heading).collect(),
+            alignments: columns.iter().map(|(_, align)| *align).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row with a cell for each column. Missing cells are left empty.
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.rows.push(Row::Cells(cells));
+    }
+
+    /// Add a rule like the one under the headings, to set off a total.
+    pub fn add_rule(&mut self) {
+        self.rows.push(Row::Rule);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headings.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            if let Row::Cells(cells) = row {
+                for (width, cell) in widths.iter_mut().zip(cells) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+        }
+        widths
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+        let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+
+        let write_line = |f: &mut fmt::Formatter<'_>, cells: &[&str]| -> fmt::Result {
+            let line = widths
+                .iter()
+                .zip(&self.alignments)
+                .enumerate()
+                .map(|(i, (&width, align))| {
+                    let cell = cells.get(i).copied().unwrap_or_default();
+                    match align {
+                        Align::Left => format!("{:<width$}", cell),
+                        Align::Right => format!("{:>width$}", cell),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())
+        };
+
+        write_line(f, &self.headings)?;
+        write_line(f, &rule.iter().map(String::as_str).collect::<Vec<&str>>())?;
+        for row in &self.rows {
+            let cells = match row {
+                Row::Cells(cells) => cells,
+                Row::Rule => &rule,
+            };
+            write_line(f, &cells.iter().map(String::as_str).collect::<Vec<&str>>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Align, Table};
+
+    #[test]
+    fn columns_line_up() {
+        let mut table = Table::new(&[("Name", Align::Left), ("Time", Align::Right)]);
+        table.add_row(vec!["parse".to_string(), "1.500 ms".to_string()]);
+        table.add_row(vec!["part 1".to_string()]);
+        table.add_rule();
+        table.add_row(vec!["total".to_string(), "12.000 ms".to_string()]);
+
+        assert!(
+            table.to_string()
+                == "Name         Time\n\
+                    ------  ---------\n\
+                    parse    1.500 ms\n\
+                    part 1\n\
+                    ------  ---------\n\
+                    total   12.000 ms\n"
+        );
+    }
+}
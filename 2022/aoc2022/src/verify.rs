@@ -0,0 +1,82 @@
+use crate::answers::{self, ExpectedAnswers};
+use aoc::{Answer, Day, Part};
+use std::path::Path;
+
+/// Run every part of a day against each input file in its expected answers file, and report any
+/// answers that differ. Returns the number of mismatched answers.
+pub fn verify_day(data_path: &Path, day: &Day) -> usize {
+    let day_path = data_path.join(day.directory_name());
+    let answers_path = day_path.join(answers::FILENAME);
+
+    if !answers_path.exists() {
+        println!(
+            "Day {}: no expected answers in {}",
+            day.number(),
+            answers_path.display()
+        );
+        return 0;
+    }
+
+    let expected_answers = match ExpectedAnswers::read(&answers_path) {
+        Ok(answers) => answers,
+        Err(message) => {
+            println!("Day {}: {}", day.number(), message);
+            return 1;
+        }
+    };
+
+    let mut mismatches = 0;
+
+    for file in expected_answers.files() {
+        let input_path = day_path.join(file);
+        let input = match aoc::crypt::read_input(&input_path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!(
+                    "Day {} {}: unable to read {}: {}",
+                    day.number(),
+                    file,
+                    input_path.display(),
+                    e
+                );
+                mismatches += 1;
+                continue;
+            }
+        };
+
+        for part in Part::all() {
+            let Some(expected) = expected_answers.get(file, *part) else {
+                continue;
+            };
+
+            let run = match day.run(&input, *part) {
+                Ok(run) => run,
+                Err(error) => {
+                    println!("Day {} {} part {}: PARSE ERROR", day.number(), file, part);
+                    println!("{}", error.with_file(input_path.display().to_string()));
+                    mismatches += 1;
+                    continue;
+                }
+            };
+
+            if run.answer == *expected {
+                println!("Day {} {} part {}: ok", day.number(), file, part);
+            } else {
+                println!("Day {} {} part {}: MISMATCH", day.number(), file, part);
+                print_labeled_answer("expected", expected);
+                print_labeled_answer("got", &run.answer);
+                mismatches += 1;
+            }
+        }
+    }
+
+    mismatches
+}
+
+fn print_labeled_answer(label: &str, answer: &Answer) {
+    if answer.is_multiline() {
+        println!("  {}:\n{}", label, answer);
+    } else {
+        println!("  {}: {}", label, answer);
+    }
+}
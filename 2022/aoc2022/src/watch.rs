@@ -0,0 +1,435 @@
+use crate::isolation::{self, Outcome};
+use aoc::{Day, Part};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Solve the chosen parts of a day with each input at `paths`, and again for any of them that
+/// change, printing how the answers differ from the previous run. An input counts as changed if
+/// either it or its encrypted copy is written. Only returns if the files can't be watched.
+pub fn watch(day: &Day, paths: &[PathBuf], parts: &[Part], timeout: Duration) -> io::Result<()> {
+    let mut watcher = Watcher::new(paths)?;
+    let mut previous: Vec<Vec<Option<String>>> = vec![vec![None; parts.len()]; paths.len()];
+    let mut changed = paths.to_vec();
+
+    loop {
+        for (path, previous) in paths.iter().zip(previous.iter_mut()) {
+            if changed.contains(path) {
+                solve(day, path, parts, timeout, previous);
+            }
+        }
+
+        changed = watcher.wait_for_change()?;
+    }
+}
+
+/// Solve the chosen parts of a day with the input at `path`, printing how the answers differ from
+/// the `previous` ones, which are replaced.
+fn solve(
+    day: &Day,
+    path: &Path,
+    parts: &[Part],
+    timeout: Duration,
+    previous: &mut [Option<String>],
+) {
+    println!("----- Day {}: {} -----", day.number(), path.display());
+
+    let input: Arc<str> = match aoc::crypt::read_input(path) {
+        Ok(input) => input.into(),
+        // The file may be missing for a moment while an editor replaces it.
+        Err(e) => {
+            eprintln!("Unable to read {}: {}", path.display(), e);
+            return;
+        }
+    };
+
+    for (part, previous) in parts.iter().zip(previous.iter_mut()) {
+        let outcome = isolation::run_isolated(*day, input.clone().into(), *part, timeout);
+        let current = match &outcome {
+            Outcome::Solved(run) => run.answer.to_string(),
+            Outcome::ParseError(error) => {
+                eprintln!("{}", error.clone().with_file(path.display().to_string()));
+                outcome.failure().unwrap_or_default().to_uppercase()
+            }
+            outcome => {
+                eprintln!("Part {}: {}", part, outcome);
+                outcome.failure().unwrap_or_default().to_uppercase()
+            }
+        };
+
+        println!("{}", describe_change(*part, previous.as_deref(), &current));
+        *previous = Some(current);
+    }
+}
+
+/// Every input and example in a day's directory: `input*.txt` and `*example*.txt`, whether they're
+/// encrypted or not. Encrypted files are given by the name they have decrypted, which is how
+/// they're read.
+pub fn day_files(day_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(day_dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .map(|path| {
+            if aoc::crypt::is_encrypted_path(&path) {
+                path.with_extension("")
+            } else {
+                path
+            }
+        })
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with(".txt") && (name.starts_with("input") || name.contains("example"))
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// The answer to a part, and how it differs from the answer the previous time it was run.
+///
+/// Answers on one line are shown with the one they replaced, like `Part 1: 45000 (was 24000)`.
+/// Answers that span several lines are shown line by line, with `-` before lines that were removed
+/// and `+` before lines that were added.
+pub fn describe_change(part: Part, previous: Option<&str>, current: &str) -> String {
+    let Some(previous) = previous else {
+        return if current.contains('\n') {
+            format!("Part {}:\n{}", part, current)
+        } else {
+            format!("Part {}: {}", part, current)
+        };
+    };
+
+    if previous == current {
+        return if current.contains('\n') {
+            format!("Part {} (unchanged):\n{}", part, current)
+        } else {
+            format!("Part {}: {} (unchanged)", part, current)
+        };
+    }
+
+    if !previous.contains('\n') && !current.contains('\n') {
+        return format!("Part {}: {} (was {})", part, current, previous);
+    }
+
+    let mut description = format!("Part {} changed:", part);
+    let previous_lines: Vec<&str> = previous.lines().collect();
+    let current_lines: Vec<&str> = current.lines().collect();
+    for i in 0..previous_lines.len().max(current_lines.len()) {
+        match (previous_lines.get(i), current_lines.get(i)) {
+            (Some(old), Some(new)) if old == new => description.push_str(&format!("\n  {}", new)),
+            (old, new) => {
+                if let Some(old) = old {
+                    description.push_str(&format!("\n- {}", old));
+                }
+                if let Some(new) = new {
+                    description.push_str(&format!("\n+ {}", new));
+                }
+            }
+        }
+    }
+    description
+}
+
+/// How long to wait for an editor to finish saving before running again, so one save runs once.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Waits for inputs to change, using inotify.
+///
+/// The files' directories are watched rather than the files themselves, because many editors save
+/// by writing a new file and renaming it over the old one, which a watch on the old file would
+/// miss.
+#[cfg(target_os = "linux")]
+struct Watcher {
+    fd: std::os::fd::OwnedFd,
+    /// The directory each watch descriptor is for.
+    directories: HashMap<libc::c_int, PathBuf>,
+    /// The input each watched file belongs to: itself, or the input it's the encrypted copy of.
+    inputs: HashMap<PathBuf, PathBuf>,
+}
+
+#[cfg(target_os = "linux")]
+impl Watcher {
+    fn new(paths: &[PathBuf]) -> io::Result<Watcher> {
+        use std::os::fd::FromRawFd;
+        use std::os::unix::ffi::OsStrExt;
+
+        // SAFETY: inotify_init1 takes no pointers, and the descriptor it returns is ours alone.
+        let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) };
+
+        let inputs = watched_files(paths)?;
+        let mut directories = HashMap::new();
+        for path in inputs.keys() {
+            let (directory, _) = split_path(path)?;
+            let mut directory_name = directory.as_os_str().as_bytes().to_vec();
+            directory_name.push(0);
+            let mask = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO | libc::IN_CREATE;
+            // SAFETY: the path is NUL terminated and outlives the call.
+            let watch = unsafe {
+                libc::inotify_add_watch(
+                    std::os::fd::AsRawFd::as_raw_fd(&fd),
+                    directory_name.as_ptr().cast(),
+                    mask,
+                )
+            };
+            if watch < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // Watching a directory twice gives the same descriptor.
+            directories.insert(watch, directory);
+        }
+
+        Ok(Watcher {
+            fd,
+            directories,
+            inputs,
+        })
+    }
+
+    /// Block until inputs have been written to or replaced, and return which.
+    fn wait_for_change(&mut self) -> io::Result<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        while changed.is_empty() {
+            self.read_changes(&mut changed)?;
+        }
+
+        // Saving often makes several events in a row, so let them all arrive.
+        while self.poll(SETTLE_TIME)? {
+            self.read_changes(&mut changed)?;
+        }
+        changed.sort();
+        changed.dedup();
+        Ok(changed)
+    }
+
+    /// Block until there are events, and add the inputs they're about to `changed`.
+    fn read_changes(&mut self, changed: &mut Vec<PathBuf>) -> io::Result<()> {
+        for (watch, name) in self.read_events()? {
+            let Some(directory) = self.directories.get(&watch) else {
+                continue;
+            };
+            if let Some(input) = self.inputs.get(&directory.join(name)) {
+                changed.push(input.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether there are events to read within `timeout`.
+    fn poll(&self, timeout: Duration) -> io::Result<bool> {
+        let mut poll_fd = libc::pollfd {
+            fd: std::os::fd::AsRawFd::as_raw_fd(&self.fd),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: poll_fd is valid for the length of the call.
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int) };
+        if ready < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(ready > 0)
+    }
+
+    /// Block until there are events, and return the watch each is from and the name of the file
+    /// it's about.
+    fn read_events(&mut self) -> io::Result<Vec<(libc::c_int, OsString)>> {
+        use std::os::unix::ffi::OsStrExt;
+
+        const HEADER_SIZE: usize = std::mem::size_of::<libc::inotify_event>();
+
+        let mut buffer = [0u8; 4096];
+        // SAFETY: the buffer is valid for writes of its whole length.
+        let length = unsafe {
+            libc::read(
+                std::os::fd::AsRawFd::as_raw_fd(&self.fd),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+            )
+        };
+        if length < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut events = Vec::new();
+        let mut offset = 0;
+        while offset + HEADER_SIZE <= length as usize {
+            // SAFETY: the kernel wrote a whole event header here. It may not be aligned.
+            let event: libc::inotify_event =
+                unsafe { std::ptr::read_unaligned(buffer[offset..].as_ptr().cast()) };
+            let name_start = offset + HEADER_SIZE;
+            let name = &buffer[name_start..name_start + event.len as usize];
+            // The name is padded with NULs.
+            let name = name.split(|&b| b == 0).next().unwrap_or_default();
+            events.push((event.wd, std::ffi::OsStr::from_bytes(name).to_os_string()));
+            offset = name_start + event.len as usize;
+        }
+        Ok(events)
+    }
+}
+
+/// Waits for inputs to change by checking when they were last modified.
+#[cfg(not(target_os = "linux"))]
+struct Watcher {
+    /// The input each watched file belongs to, and when the file was last modified.
+    files: HashMap<PathBuf, (PathBuf, Option<std::time::SystemTime>)>,
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Watcher {
+    fn new(paths: &[PathBuf]) -> io::Result<Watcher> {
+        let files = watched_files(paths)?
+            .into_iter()
+            .map(|(path, input)| {
+                let modified = Self::modified(&path);
+                (path, (input, modified))
+            })
+            .collect();
+        Ok(Watcher { files })
+    }
+
+    fn modified(path: &Path) -> Option<std::time::SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    fn wait_for_change(&mut self) -> io::Result<Vec<PathBuf>> {
+        loop {
+            std::thread::sleep(SETTLE_TIME);
+            let mut changed = Vec::new();
+            for (path, (input, modified)) in self.files.iter_mut() {
+                let now = Self::modified(path);
+                if now != *modified {
+                    *modified = now;
+                    changed.push(input.clone());
+                }
+            }
+            if !changed.is_empty() {
+                changed.sort();
+                changed.dedup();
+                return Ok(changed);
+            }
+        }
+    }
+}
+
+/// The files to watch for each input, which are the input and its encrypted copy, with the input
+/// each belongs to.
+fn watched_files(paths: &[PathBuf]) -> io::Result<HashMap<PathBuf, PathBuf>> {
+    let mut files = HashMap::new();
+    for path in paths {
+        split_path(path)?;
+        files.insert(path.clone(), path.clone());
+        files.insert(aoc::crypt::encrypted_path(path), path.clone());
+    }
+    Ok(files)
+}
+
+/// The directory a file is in, and its name.
+fn split_path(path: &Path) -> io::Result<(PathBuf, OsString)> {
+    let Some(file_name) = path.file_name() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file", path.display()),
+        ));
+    };
+
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    Ok((directory, file_name.to_os_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{day_files, describe_change, Watcher};
+    use aoc::Part;
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn changes_are_described() {
+        assert!(describe_change(Part::One, None, "24000") == "Part 1: 24000");
+        assert!(describe_change(Part::One, Some("24000"), "24000") == "Part 1: 24000 (unchanged)");
+        assert!(describe_change(Part::Two, Some("24000"), "45000") == "Part 2: 45000 (was 24000)");
+        assert!(
+            describe_change(Part::Two, Some("##\n.."), "##\n#.\n##")
+                == "Part 2 changed:\n  ##\n- ..\n+ #.\n+ ##"
+        );
+    }
+
+    #[test]
+    fn inputs_and_examples_are_found() {
+        let directory =
+            std::env::temp_dir().join(format!("aoc-watch-files-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        for name in [
+            "input.txt.enc",
+            "input-head-2.txt",
+            "example.txt",
+            "small-example.txt",
+            "answers.txt",
+            "title.txt",
+        ] {
+            fs::write(directory.join(name), "").unwrap();
+        }
+
+        let files = day_files(&directory).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert!(
+            names
+                == [
+                    "example.txt",
+                    "input-head-2.txt",
+                    "input.txt",
+                    "small-example.txt"
+                ]
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn writing_the_files_is_noticed() {
+        let directory = std::env::temp_dir().join(format!("aoc-watch-test-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let input = directory.join("input.txt");
+        let example = directory.join("example.txt");
+        fs::write(&example, "1").unwrap();
+
+        let mut watcher = Watcher::new(&[example.clone(), input.clone()]).unwrap();
+        let writer = {
+            let directory = directory.clone();
+            let example = example.clone();
+            let input = input.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(200));
+                // Other files in the directory don't count.
+                fs::write(directory.join("answers.txt"), "2").unwrap();
+                fs::write(&example, "2").unwrap();
+                thread::sleep(Duration::from_millis(400));
+                fs::write(aoc::crypt::encrypted_path(&input), "3").unwrap();
+            })
+        };
+
+        assert!(watcher.wait_for_change().unwrap() == [example.clone()]);
+        assert!(fs::read_to_string(&example).unwrap() == "2");
+        // Writing the encrypted copy changes the input it's a copy of.
+        assert!(watcher.wait_for_change().unwrap() == [input]);
+
+        writer.join().unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
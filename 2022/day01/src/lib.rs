@@ -0,0 +1,157 @@
+use aoc::{Generator, ParseError, Rng, Solution};
+use std::io::BufRead;
+
+/// The calorie totals of the three elves carrying the most, most first, read an elf at a time so
+/// an input of any length takes the same memory. There are zeros in place of missing elves.
+///
+/// Keeping all the totals would take memory for every elf, so the top three are picked out here,
+/// as they're read, which is as many as either part looks at.
+pub fn get_top_calorie_totals(reader: impl BufRead) -> Result<[u64; 3], ParseError> {
+    let mut top = [0; 3];
+    let mut elf: Option<u64> = None;
+
+    let mut add_elf = |total: u64| {
+        if let Some(i) = top.iter().position(|&t| total > t) {
+            top[i..].rotate_right(1);
+            top[i] = total;
+        }
+    };
+
+    aoc::read_lines(reader, |line| {
+        if line.trim().is_empty() {
+            if let Some(total) = elf.take() {
+                add_elf(total);
+            }
+        } else {
+            let calories = aoc::parse_number::<u64>(line, line)?;
+            let total = elf
+                .unwrap_or_default()
+                .checked_add(calories)
+                .ok_or_else(|| {
+                    ParseError::at(line, 1, "The elf is carrying too many calories to add up")
+                })?;
+            elf = Some(total);
+        }
+        Ok(())
+    })?;
+    if let Some(total) = elf {
+        add_elf(total);
+    }
+
+    Ok(top)
+}
+
+/// Add an elf carrying up to 15 snacks of up to 60000 calories each.
+fn generate_elf(rng: &mut Rng, input: &mut String) {
+    for _ in 0..rng.between(1, 15) {
+        input.push_str(&format!("{}\n", rng.between(1000, 60000)));
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = [u64; 3];
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_top_calorie_totals(input.as_bytes())
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, ParseError> {
+        get_top_calorie_totals(reader)
+    }
+
+    /// The elf with the highest calorie count in their knapsack.
+    fn part1(elves: &Self::Input) -> u64 {
+        elves[0]
+    }
+
+    /// The sum of the calorie counts of the three elves with the most calories in their knapsacks.
+    fn part2(elves: &Self::Input) -> u64 {
+        elves.iter().sum()
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![
+            Generator::new(
+                "random",
+                "Elves with up to 15 snacks each",
+                |rng, size, input| {
+                    while input.len() < size {
+                        if !input.is_empty() {
+                            input.push('\n');
+                        }
+                        generate_elf(rng, input);
+                    }
+                },
+            ),
+            Generator::new(
+                "few-elves",
+                "One or two elves, whatever the size, so there's no third for part 2",
+                |rng, _, input| {
+                    generate_elf(rng, input);
+                    if rng.between(0, 1) == 1 {
+                        input.push('\n');
+                        generate_elf(rng, input);
+                    }
+                },
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::get_top_calorie_totals;
+    use aoc::{Generated, Rng};
+    use std::io::BufReader;
+
+    /// An input of about `size` bytes, with elves carrying up to 20 snacks of up to 60000
+    /// calories, and the biggest eaters in the middle of it.
+    fn generated_input(size: u64) -> BufReader<impl std::io::Read> {
+        let mut rng = Rng::new(1);
+        let mut written = 0;
+        let mut planted = false;
+        BufReader::new(Generated::new(size, move |elf: &mut String| {
+            if !planted && written > size / 2 {
+                planted = true;
+                elf.push_str("9000000\n\n8000000\n1\n\n7000000\n\n");
+            }
+            for _ in 0..rng.between(1, 20) {
+                elf.push_str(&format!("{}\n", rng.between(1, 60000)));
+            }
+            elf.push('\n');
+            written += elf.len() as u64;
+        }))
+    }
+
+    #[test]
+    fn example() {
+        let example = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        assert!(get_top_calorie_totals(example.as_bytes()) == Ok([24000, 11000, 10000]));
+        assert!(get_top_calorie_totals("5\n\n\n6\n".as_bytes()) == Ok([6, 5, 0]));
+        assert!(get_top_calorie_totals("5\nx\n".as_bytes()).is_err());
+
+        let too_many = format!("{}\n1\n", u64::MAX);
+        let error = get_top_calorie_totals(too_many.as_bytes()).unwrap_err();
+        assert!(error.line_number() == Some(2));
+    }
+
+    #[test]
+    fn large_inputs_take_little_memory() {
+        let (top, memory) =
+            aoc::memory::measure(|| get_top_calorie_totals(generated_input(1 << 22)));
+        assert!(top == Ok([9000000, 8000001, 7000000]));
+        assert!(memory.unwrap().peak_bytes < 64 * 1024);
+    }
+
+    /// Run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn multi_gigabyte_input() {
+        let top = get_top_calorie_totals(generated_input(4 << 30));
+        assert!(top == Ok([9000000, 8000001, 7000000]));
+    }
+}
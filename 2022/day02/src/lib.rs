@@ -0,0 +1,311 @@
+use aoc::{Generator, ParseError, Solution};
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+const SCORE_FOR_ROCK: i32 = 1;
+const SCORE_FOR_PAPER: i32 = 2;
+const SCORE_FOR_SCISSORS: i32 = 3;
+
+const SCORE_FOR_WIN: i32 = 6;
+const SCORE_FOR_DRAW: i32 = 3;
+const SCORE_FOR_LOSS: i32 = 0;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    fn part1_from_string(s: &str) -> Option<Shape> {
+        if s == "A" || s == "X" {
+            Some(Shape::Rock)
+        } else if s == "B" || s == "Y" {
+            Some(Shape::Paper)
+        } else if s == "C" || s == "Z" {
+            Some(Shape::Scissors)
+        } else {
+            None
+        }
+    }
+
+    fn part2_from_string(s: &str) -> Option<Shape> {
+        if s == "A" {
+            Some(Shape::Rock)
+        } else if s == "B" {
+            Some(Shape::Paper)
+        } else if s == "C" {
+            Some(Shape::Scissors)
+        } else {
+            None
+        }
+    }
+
+    fn part2_from_shape_and_outcome(shape: Shape, outcome: Outcome) -> Shape {
+        match outcome {
+            Outcome::Win => shape.shape_to_win(),
+            Outcome::Draw => shape.shape_to_draw(),
+            Outcome::Loss => shape.shape_to_lose(),
+        }
+    }
+
+    fn shape_to_win(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
+    }
+
+    fn shape_to_draw(&self) -> Shape {
+        *self
+    }
+
+    fn shape_to_lose(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    fn part2_from_string(s: &str) -> Option<Outcome> {
+        match s {
+            "X" => Some(Outcome::Loss),
+            "Y" => Some(Outcome::Draw),
+            "Z" => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+
+    fn with_shapes(opponent: Shape, me: Shape) -> Outcome {
+        match (opponent, me) {
+            (Shape::Rock, Shape::Rock) => Outcome::Draw,
+            (Shape::Rock, Shape::Paper) => Outcome::Win,
+            (Shape::Rock, Shape::Scissors) => Outcome::Loss,
+            (Shape::Paper, Shape::Rock) => Outcome::Loss,
+            (Shape::Paper, Shape::Paper) => Outcome::Draw,
+            (Shape::Paper, Shape::Scissors) => Outcome::Win,
+            (Shape::Scissors, Shape::Rock) => Outcome::Win,
+            (Shape::Scissors, Shape::Paper) => Outcome::Loss,
+            (Shape::Scissors, Shape::Scissors) => Outcome::Draw,
+        }
+    }
+}
+
+struct Round {
+    me: Shape,
+    outcome: Outcome,
+}
+
+impl Round {
+    fn part1_from_string(s: &str) -> Option<Round> {
+        let split: Vec<&str> = s.split(' ').collect();
+        match (
+            Shape::part1_from_string(split[0]),
+            Shape::part1_from_string(split[1]),
+        ) {
+            (Some(opponents_shape), Some(my_shape)) => Some(Round {
+                me: my_shape,
+                outcome: Outcome::with_shapes(opponents_shape, my_shape),
+            }),
+            _ => None,
+        }
+    }
+
+    fn part2_from_string(s: &str) -> Option<Round> {
+        let split: Vec<&str> = s.split(' ').collect();
+        match (
+            Shape::part2_from_string(split[0]),
+            Outcome::part2_from_string(split[1]),
+        ) {
+            (Some(opponents_shape), Some(expected_outcome)) => Some(Round {
+                me: Shape::part2_from_shape_and_outcome(opponents_shape, expected_outcome),
+                outcome: expected_outcome,
+            }),
+            _ => None,
+        }
+    }
+
+    fn score(&self) -> i32 {
+        self.score_for_my_shape() + self.score_for_outcome()
+    }
+
+    fn score_for_my_shape(&self) -> i32 {
+        match self.me {
+            Shape::Rock => SCORE_FOR_ROCK,
+            Shape::Paper => SCORE_FOR_PAPER,
+            Shape::Scissors => SCORE_FOR_SCISSORS,
+        }
+    }
+
+    fn score_for_outcome(&self) -> i32 {
+        match self.outcome {
+            Outcome::Win => SCORE_FOR_WIN,
+            Outcome::Draw => SCORE_FOR_DRAW,
+            Outcome::Loss => SCORE_FOR_LOSS,
+        }
+    }
+}
+
+/// How many times each round comes up in the strategy guide. There are only nine different rounds,
+/// so a guide of any length is kept in the same memory, and each part scores them its own way.
+pub struct Rounds {
+    counts: BTreeMap<String, u64>,
+}
+
+impl Rounds {
+    fn total_score(&self, round_from_string: fn(&str) -> Option<Round>) -> u64 {
+        self.counts
+            .iter()
+            .filter_map(|(line, count)| round_from_string(line).map(|round| (round, count)))
+            .map(|(round, count)| round.score() as u64 * count)
+            .sum()
+    }
+}
+
+/// Check each round of the strategy guide and count them up, a line at a time.
+pub fn read_strategy_guide(reader: impl BufRead) -> Result<Rounds, ParseError> {
+    let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+
+    aoc::read_lines(reader, |line| {
+        let mut split = line.split(' ');
+
+        let opponent = split.next().unwrap_or_default();
+        if Shape::part2_from_string(opponent).is_none() {
+            return Err(ParseError::at_token(
+                line,
+                opponent,
+                format!("Expected A, B or C, found '{}'", opponent),
+            ));
+        }
+
+        let Some(response) = split.next() else {
+            return Err(ParseError::at(
+                line,
+                line.len() + 1,
+                "Missing second column",
+            ));
+        };
+        if Outcome::part2_from_string(response).is_none() {
+            return Err(ParseError::at_token(
+                line,
+                response,
+                format!("Expected X, Y or Z, found '{}'", response),
+            ));
+        }
+
+        if let Some(extra) = split.next() {
+            return Err(ParseError::at_token(line, extra, "Unexpected third column"));
+        }
+
+        match counts.get_mut(line) {
+            Some(count) => *count += 1,
+            None => {
+                counts.insert(line.to_string(), 1);
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(Rounds { counts })
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Rounds;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_strategy_guide(input.as_bytes())
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, ParseError> {
+        read_strategy_guide(reader)
+    }
+
+    /// The total score, reading both columns of the strategy guide as shapes.
+    fn part1(rounds: &Self::Input) -> u64 {
+        rounds.total_score(Round::part1_from_string)
+    }
+
+    /// The total score, reading the second column of the strategy guide as the expected outcome.
+    fn part2(rounds: &Self::Input) -> u64 {
+        rounds.total_score(Round::part2_from_string)
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![Generator::new(
+            "random",
+            "Rounds of random shapes and outcomes",
+            |rng, size, input| {
+                while input.len() < size {
+                    input.push_str(rng.choose(&["A", "B", "C"]).unwrap());
+                    input.push(' ');
+                    input.push_str(rng.choose(&["X", "Y", "Z"]).unwrap());
+                    input.push('\n');
+                }
+            },
+        )]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{read_strategy_guide, Day2};
+    use aoc::{Generated, Rng, Solution};
+    use std::io::BufReader;
+
+    /// An input of about `size` bytes, and the score it has for both parts. The rounds come in
+    /// shuffled cycles of all nine, which score 45 each way.
+    fn generated_input(size: u64) -> (BufReader<impl std::io::Read>, u64) {
+        const ROUNDS: [&str; 9] = [
+            "A X\n", "A Y\n", "A Z\n", "B X\n", "B Y\n", "B Z\n", "C X\n", "C Y\n", "C Z\n",
+        ];
+        let cycles = size / 36;
+        let mut rng = Rng::new(2);
+        let reader = BufReader::new(Generated::new(cycles * 36, move |cycle: &mut String| {
+            let mut rounds = ROUNDS;
+            rng.shuffle(&mut rounds);
+            cycle.push_str(&rounds.concat());
+        }));
+        (reader, cycles * 45)
+    }
+
+    #[test]
+    fn example() {
+        let rounds = read_strategy_guide("A Y\nB X\nC Z\n".as_bytes()).unwrap();
+        assert!(Day2::part1(&rounds) == 15 && Day2::part2(&rounds) == 12);
+        let error = read_strategy_guide("A Y\nB W\n".as_bytes()).err().unwrap();
+        assert!(error.line_number() == Some(2));
+    }
+
+    #[test]
+    fn large_inputs_take_little_memory() {
+        let (input, score) = generated_input(1 << 22);
+        let (rounds, memory) = aoc::memory::measure(|| read_strategy_guide(input).unwrap());
+        assert!(Day2::part1(&rounds) == score && Day2::part2(&rounds) == score);
+        assert!(memory.unwrap().peak_bytes < 64 * 1024);
+    }
+
+    /// Run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn multi_gigabyte_input() {
+        let (input, score) = generated_input(4 << 30);
+        let rounds = read_strategy_guide(input).unwrap();
+        assert!(Day2::part1(&rounds) == score && Day2::part2(&rounds) == score);
+    }
+}
@@ -0,0 +1,153 @@
+use aoc::{Generator, ParseError, Rng, Solution};
+use std::collections::HashSet;
+use std::iter::Iterator;
+
+const LOWERCASE_A_SCORE: u32 = 'a' as u32;
+const UPPERCASE_A_SCORE: u32 = 'A' as u32;
+
+fn priority_items_in_knapsack(knapsack: &str) -> Vec<char> {
+    assert!(knapsack.len().is_multiple_of(2));
+
+    let compartment_size: usize = knapsack.len() / 2;
+
+    let (left_compartment, right_compartment) = knapsack.split_at(compartment_size);
+    let left_compartment_set: HashSet<char> = HashSet::from_iter(left_compartment.chars());
+    let right_compartment_set: HashSet<char> = HashSet::from_iter(right_compartment.chars());
+
+    left_compartment_set
+        .intersection(&right_compartment_set)
+        .copied()
+        .collect()
+}
+
+fn badge_for_group(group: (&str, &str, &str)) -> Vec<char> {
+    let a_set: HashSet<char> = HashSet::from_iter(group.0.chars());
+    let b_set: HashSet<char> = HashSet::from_iter(group.1.chars());
+    let c_set: HashSet<char> = HashSet::from_iter(group.2.chars());
+
+    let ab_intersection: HashSet<char> = a_set.intersection(&b_set).copied().collect();
+    ab_intersection.intersection(&c_set).copied().collect()
+}
+
+fn score(c: &char) -> u32 {
+    let char_value: u32 = (*c).into();
+    if c.is_lowercase() {
+        char_value - LOWERCASE_A_SCORE + 1
+    } else {
+        char_value - UPPERCASE_A_SCORE + 27
+    }
+}
+
+/// Add a group of three knapsacks, each with one item in both of its compartments, and a badge
+/// that's the only item in all three.
+fn generate_group(rng: &mut Rng, input: &mut String) {
+    let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    rng.shuffle(&mut items);
+    let (badge, others) = items.split_first().unwrap();
+
+    // Each knapsack has its own items apart from the badge, so nothing else is in all three.
+    for own in others.chunks(others.len() / 3) {
+        let (shared, own) = own.split_first().unwrap();
+        let (left_only, right_only) = own.split_at(own.len() / 2);
+
+        let size = rng.between(2, 16) as usize;
+        let mut compartments = [left_only, right_only].map(|only| {
+            let mut compartment: Vec<char> =
+                (1..size).map(|_| *rng.choose(only).unwrap()).collect();
+            compartment.push(*shared);
+            compartment
+        });
+        let with_badge = rng.between(0, 1) as usize;
+        compartments[with_badge][0] = *badge;
+
+        for compartment in &mut compartments {
+            rng.shuffle(compartment);
+            input.extend(compartment.iter());
+        }
+        input.push('\n');
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let knapsacks = aoc::parse_lines(input, |line| {
+            if let Some((column, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::at(
+                    line,
+                    column + 1,
+                    format!("Expected an item letter, found '{}'", c),
+                ));
+            }
+            if !line.len().is_multiple_of(2) {
+                return Err(ParseError::at(
+                    line,
+                    line.len(),
+                    "Knapsack compartments must hold the same number of items",
+                ));
+            }
+            Ok(line.to_string())
+        })?;
+
+        if !knapsacks.len().is_multiple_of(3) {
+            return Err(ParseError::new(format!(
+                "Expected groups of three knapsacks, found {} knapsacks",
+                knapsacks.len()
+            ))
+            .with_line_number(knapsacks.len()));
+        }
+
+        Ok(knapsacks)
+    }
+
+    /// The sum of the scores of all priority items.
+    fn part1(knapsacks: &Self::Input) -> u32 {
+        knapsacks
+            .iter()
+            .flat_map(|knapsack| priority_items_in_knapsack(knapsack))
+            .map(|c| score(&c))
+            .sum()
+    }
+
+    /// The sum of the scores of the badges of every group of three elves.
+    fn part2(knapsacks: &Self::Input) -> u32 {
+        knapsacks
+            .chunks(3)
+            .flat_map(|group| badge_for_group((&group[0], &group[1], &group[2])))
+            .map(|c| score(&c))
+            .sum()
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![Generator::new(
+            "random",
+            "Groups of three knapsacks of 4 to 32 items",
+            |rng, size, input| {
+                while input.len() < size {
+                    generate_group(rng, input);
+                }
+            },
+        )]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day3;
+    use aoc::Solution;
+
+    #[test]
+    fn generated_inputs_are_seeded_and_parse() {
+        for generator in Day3::generators() {
+            let input = generator.generate(2022, 2000);
+            assert!(input == generator.generate(2022, 2000));
+            assert!(input != generator.generate(2023, 2000));
+            assert!(Day3::parse(&input).is_ok());
+        }
+    }
+}
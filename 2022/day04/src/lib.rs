@@ -0,0 +1,209 @@
+use aoc::{Generator, ParseError, Solution};
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+/// Parse a range specifier like `2-4`, which is a slice of `line`.
+fn parse_range(line: &str, specifier: &str) -> Result<RangeInclusive<u32>, ParseError> {
+    let Some((start, end)) = specifier.split_once('-') else {
+        return Err(ParseError::at_token(
+            line,
+            specifier,
+            format!("Expected a range like 2-4, found '{}'", specifier),
+        ));
+    };
+    let start = aoc::parse_number::<u32>(line, start)?;
+    let end = aoc::parse_number::<u32>(line, end)?;
+    Ok(start..=end)
+}
+
+pub struct Assignment {
+    left: RangeInclusive<u32>,
+    right: RangeInclusive<u32>,
+}
+
+impl Assignment {
+    fn from_line(line: &str) -> Result<Assignment, ParseError> {
+        let Some((left_specifier, right_specifier)) = line.split_once(',') else {
+            return Err(ParseError::at(
+                line,
+                line.len() + 1,
+                "Expected two ranges separated by ','",
+            ));
+        };
+
+        Ok(Assignment {
+            left: parse_range(line, left_specifier)?,
+            right: parse_range(line, right_specifier)?,
+        })
+    }
+
+    /// The same assignment with each section number replaced by how many of the four ends are
+    /// below it. That keeps whether either range contains or overlaps the other, and there are
+    /// only a few dozen ways the ends can fall.
+    fn normalized(&self) -> [u32; 4] {
+        let ends = [
+            *self.left.start(),
+            *self.left.end(),
+            *self.right.start(),
+            *self.right.end(),
+        ];
+        ends.map(|end| ends.iter().filter(|&&other| other < end).count() as u32)
+    }
+
+    fn from_normalized(ends: &[u32; 4]) -> Assignment {
+        Assignment {
+            left: ends[0]..=ends[1],
+            right: ends[2]..=ends[3],
+        }
+    }
+
+    fn has_range_contained_by_other(&self) -> bool {
+        let left_contains_right =
+            self.left.contains(self.right.start()) && self.left.contains(self.right.end());
+        let right_contains_left =
+            self.right.contains(self.left.start()) && self.right.contains(self.left.end());
+
+        left_contains_right || right_contains_left
+    }
+
+    fn has_overlapping_range(&self) -> bool {
+        self.left.contains(self.right.start())
+            || self.left.contains(self.right.end())
+            || self.right.contains(self.left.start())
+            || self.right.contains(self.left.end())
+    }
+}
+
+/// How many assignments there are of each [Assignment::normalized] shape, so a list of any
+/// length is kept in the same memory, and each part checks the shapes its own way.
+pub struct Assignments {
+    counts: BTreeMap<[u32; 4], usize>,
+}
+
+impl Assignments {
+    fn count(&self, matches: fn(&Assignment) -> bool) -> usize {
+        self.counts
+            .iter()
+            .filter(|(ends, _)| matches(&Assignment::from_normalized(ends)))
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+/// Read the assignments a line at a time and count up their shapes.
+pub fn count_assignments(reader: impl BufRead) -> Result<Assignments, ParseError> {
+    let mut counts = BTreeMap::new();
+
+    aoc::read_lines(reader, |line| {
+        let assignment = Assignment::from_line(line)?;
+        *counts.entry(assignment.normalized()).or_insert(0) += 1;
+        Ok(())
+    })?;
+
+    Ok(Assignments { counts })
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Assignments;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        count_assignments(input.as_bytes())
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, ParseError> {
+        count_assignments(reader)
+    }
+
+    /// The number of assignments with one range containing the other.
+    fn part1(assignments: &Self::Input) -> usize {
+        assignments.count(Assignment::has_range_contained_by_other)
+    }
+
+    /// The number of assignments with ranges at least partially overlapping.
+    fn part2(assignments: &Self::Input) -> usize {
+        assignments.count(Assignment::has_overlapping_range)
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![Generator::new(
+            "random",
+            "Pairs of ranges of sections from 1 to 99, some of a single section",
+            |rng, size, input| {
+                while input.len() < size {
+                    let mut range = || {
+                        let start = rng.between(1, 99);
+                        let end = if rng.between(0, 9) == 0 {
+                            start
+                        } else {
+                            rng.between(start, 99)
+                        };
+                        format!("{}-{}", start, end)
+                    };
+                    let (left, right) = (range(), range());
+                    input.push_str(&format!("{},{}\n", left, right));
+                }
+            },
+        )]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{count_assignments, Day4};
+    use aoc::{Generated, Rng, Solution};
+    use std::io::BufReader;
+
+    /// An input of about `size` bytes, and how many of its assignments have a range inside the
+    /// other and how many overlap.
+    fn generated_input(size: u64) -> (BufReader<impl std::io::Read>, usize, usize) {
+        // Each pair of lines is 24 bytes: one inside the other, then one apart.
+        let pairs = size / 24;
+        let mut rng = Rng::new(4);
+        let reader = BufReader::new(Generated::new(pairs * 24, move |pair: &mut String| {
+            let start = rng.between(10, 49);
+            pair.push_str(&format!(
+                "{}-{},{}-{}\n{}-{},{}-{}\n",
+                start,
+                start + 40,
+                start + 1,
+                start + 2,
+                start,
+                start + 1,
+                start + 40,
+                start + 49
+            ));
+        }));
+        (reader, pairs as usize, pairs as usize)
+    }
+
+    #[test]
+    fn example() {
+        let example = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+        let assignments = count_assignments(example.as_bytes()).unwrap();
+        assert!(Day4::part1(&assignments) == 2 && Day4::part2(&assignments) == 4);
+    }
+
+    #[test]
+    fn large_inputs_take_little_memory() {
+        let (input, contained, overlapping) = generated_input(1 << 22);
+        let (assignments, memory) = aoc::memory::measure(|| count_assignments(input).unwrap());
+        assert!(Day4::part1(&assignments) == contained);
+        assert!(Day4::part2(&assignments) == overlapping);
+        assert!(memory.unwrap().peak_bytes < 64 * 1024);
+    }
+
+    /// Run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn multi_gigabyte_input() {
+        let (input, contained, overlapping) = generated_input(4 << 30);
+        let assignments = count_assignments(input).unwrap();
+        assert!(Day4::part1(&assignments) == contained);
+        assert!(Day4::part2(&assignments) == overlapping);
+    }
+}
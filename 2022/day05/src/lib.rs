@@ -0,0 +1,320 @@
+use aoc::{Generator, ParseError, Rng, Solution};
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Stacks(Vec<Vec<String>>);
+
+impl Stacks {
+    /// Move crates one at a time, which leaves them in the reverse order. Moves are checked
+    /// against the heights of the stacks when they're parsed, so there are always enough crates.
+    fn part1_perform(&mut self, instruction: &Instruction) {
+        let moved = self.take_crates(instruction);
+        self.0[instruction.to_stack].extend(moved.into_iter().rev());
+    }
+
+    /// Move crates several at once, which keeps them in the same order.
+    fn part2_perform(&mut self, instruction: &Instruction) {
+        let moved = self.take_crates(instruction);
+        self.0[instruction.to_stack].extend(moved);
+    }
+
+    fn take_crates(&mut self, instruction: &Instruction) -> Vec<String> {
+        let from_stack = &mut self.0[instruction.from_stack];
+        from_stack.split_off(from_stack.len() - instruction.quantity)
+    }
+
+    fn tops(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|s| s.last()).map(|s| s.as_str())
+    }
+}
+
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .enumerate()
+                .map(|(i, v)| format!("{}: {}", i, v.join(", ")))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    quantity: usize,
+    from_stack: usize,
+    to_stack: usize,
+}
+
+/// Stacks are numbered from 1 in the input, and indexed from 0 here.
+fn stack_index(line: &str, token: &str) -> Result<usize, ParseError> {
+    match aoc::parse_number::<usize>(line, token)? {
+        0 => Err(ParseError::at_token(
+            line,
+            token,
+            "Stacks are numbered from 1",
+        )),
+        n => Ok(n - 1),
+    }
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> std::result::Result<Instruction, Self::Error> {
+        let (quantity, from, to): (usize, &str, &str) = aoc::scan!(s, "move {} from {} to {}")?;
+
+        Ok(Instruction {
+            quantity,
+            from_stack: stack_index(s, from)?,
+            to_stack: stack_index(s, to)?,
+        })
+    }
+}
+
+/// Add a drawing of up to nine stacks of crates and about `size` bytes of moves between them. If
+/// `empty` some stacks start empty, and moves often take every crate off a stack.
+fn generate_stacks(rng: &mut Rng, size: usize, empty: bool, input: &mut String) {
+    let number_of_stacks = rng.between(2, 9) as usize;
+    let mut heights: Vec<usize> = (0..number_of_stacks)
+        .map(|_| {
+            if empty && rng.between(0, 2) == 0 {
+                0
+            } else {
+                rng.between(1, 8) as usize
+            }
+        })
+        .collect();
+    if heights.iter().all(|&height| height == 0) {
+        heights[0] = 1;
+    }
+
+    let tallest = heights.iter().max().copied().unwrap_or_default();
+    for level in (0..tallest).rev() {
+        let row: Vec<String> = heights
+            .iter()
+            .map(|&height| {
+                if level < height {
+                    format!("[{}]", (b'A' + rng.between(0, 25) as u8) as char)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+        input.push_str(&row.join(" "));
+        input.push('\n');
+    }
+    let numbers: Vec<String> = (1..=number_of_stacks).map(|n| format!(" {} ", n)).collect();
+    input.push_str(&numbers.join(" "));
+    input.push_str("\n\n");
+
+    while input.len() < size {
+        let from_stacks: Vec<usize> = (0..number_of_stacks)
+            .filter(|&stack| heights[stack] > 0)
+            .collect();
+        let from = *rng.choose(&from_stacks).unwrap();
+        let mut to = rng.between(0, number_of_stacks as u64 - 2) as usize;
+        if to >= from {
+            to += 1;
+        }
+        let quantity = if empty && rng.between(0, 2) == 0 {
+            heights[from]
+        } else {
+            rng.between(1, heights[from].min(4) as u64) as usize
+        };
+
+        heights[from] -= quantity;
+        heights[to] += quantity;
+        input.push_str(&format!(
+            "move {} from {} to {}\n",
+            quantity,
+            from + 1,
+            to + 1
+        ));
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Stacks, Vec<Instruction>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut sections = aoc::sections(input);
+
+        let Some(diagram) = sections.next() else {
+            return Err(ParseError::new("Missing stacks of crates"));
+        };
+        let Some(moves) = sections.next() else {
+            return Err(ParseError::new(
+                "Missing the blank line between the stacks and the instructions",
+            ));
+        };
+        if let Some(extra) = sections.next() {
+            return Err(
+                ParseError::new("Unexpected blank line among the instructions")
+                    .with_line_number(extra.first_line_number() - 1),
+            );
+        }
+
+        let number_of_stacks = (diagram.lines()[0].len() as f32 / 4.0).ceil() as usize;
+        let mut stacks = Stacks(vec![vec![]; number_of_stacks]);
+
+        for (line_number, line) in diagram.numbered_lines() {
+            let mut chars = line.chars().peekable();
+            let mut index_of_stack = 0;
+            while chars.peek().is_some() {
+                // Read the line in 4 character chunks.
+                let stack: Vec<char> = chars.by_ref().take(4).collect();
+                if stack[0] == '[' {
+                    let column = index_of_stack * 4 + 1;
+                    if index_of_stack >= number_of_stacks {
+                        return Err(ParseError::at(line, column, "Too many stacks")
+                            .with_line_number(line_number));
+                    }
+                    if stack.len() < 3 || stack[2] != ']' {
+                        return Err(ParseError::at(line, column, "Expected a crate like [A]")
+                            .with_line_number(line_number));
+                    }
+                    stacks.0[index_of_stack].insert(0, String::from(stack[1]));
+                }
+
+                index_of_stack += 1;
+            }
+        }
+
+        // Both parts move the same number of crates between the same stacks, so the heights of
+        // the stacks are the same for both.
+        let mut heights: Vec<usize> = stacks.0.iter().map(Vec::len).collect();
+        let instructions = moves.parse_lines(|line| {
+            let instruction = Instruction::try_from(line)?;
+            for stack in [instruction.from_stack, instruction.to_stack] {
+                if stack >= number_of_stacks {
+                    return Err(ParseError::at(
+                        line,
+                        1,
+                        format!("There is no stack {}", stack + 1),
+                    ));
+                }
+            }
+
+            let height = heights[instruction.from_stack];
+            if instruction.quantity > height {
+                // The quantity comes straight after "move ".
+                return Err(ParseError::at(
+                    line,
+                    6,
+                    format!(
+                        "Stack {} only has {} crates to move",
+                        instruction.from_stack + 1,
+                        height
+                    ),
+                ));
+            }
+            heights[instruction.from_stack] -= instruction.quantity;
+            heights[instruction.to_stack] += instruction.quantity;
+            Ok(instruction)
+        })?;
+
+        Ok((stacks, instructions))
+    }
+
+    /// The tops of the stacks after moving crates one at a time.
+    fn part1(input: &Self::Input) -> String {
+        let (stacks, instructions) = input;
+        let mut stacks = stacks.clone();
+        for instruction in instructions {
+            stacks.part1_perform(instruction);
+        }
+
+        aoc::debug!("{}", stacks);
+        stacks.tops().collect::<Vec<&str>>().join("")
+    }
+
+    /// The tops of the stacks after moving several crates at once.
+    fn part2(input: &Self::Input) -> String {
+        let (stacks, instructions) = input;
+        let mut stacks = stacks.clone();
+        for instruction in instructions {
+            stacks.part2_perform(instruction);
+        }
+
+        aoc::debug!("{}", stacks);
+        stacks.tops().collect::<Vec<&str>>().join("")
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![
+            Generator::new(
+                "random",
+                "Up to nine stacks, with moves of a few crates at a time",
+                |rng, size, input| generate_stacks(rng, size, false, input),
+            ),
+            Generator::new(
+                "empty-stacks",
+                "Stacks that start empty, and moves that empty them",
+                |rng, size, input| generate_stacks(rng, size, true, input),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day5;
+    use aoc::Solution;
+
+    const EXAMPLE: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+                           move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\n\
+                           move 1 from 1 to 2\n";
+
+    #[test]
+    fn example() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+        assert!(Day5::part1(&input) == "CMZ" && Day5::part2(&input) == "MCD");
+    }
+
+    #[test]
+    fn generated_inputs_are_seeded_and_parse() {
+        for generator in Day5::generators() {
+            let input = generator.generate(2022, 2000);
+            assert!(input == generator.generate(2022, 2000));
+            assert!(input != generator.generate(2023, 2000));
+            assert!(Day5::parse(&input).is_ok());
+        }
+    }
+
+    /// Stacks that start empty, or are emptied by a move, are still solved, and have no crate on
+    /// top.
+    #[test]
+    fn generated_empty_stacks_are_solved() {
+        let generator = Day5::generators()
+            .into_iter()
+            .find(|generator| generator.shape == "empty-stacks")
+            .unwrap();
+        let mut starts_empty = false;
+        for seed in 0..10 {
+            let input = Day5::parse(&generator.generate(seed, 2000)).unwrap();
+            let (stacks, _) = &input;
+            starts_empty |= stacks.0.iter().any(|stack| stack.is_empty());
+            assert!(Day5::part1(&input).len() <= stacks.0.len());
+            assert!(Day5::part2(&input).len() <= stacks.0.len());
+        }
+        assert!(starts_empty);
+    }
+
+    #[test]
+    fn moves_of_more_crates_than_a_stack_has_are_errors() {
+        let error = Day5::parse("[A]    \n 1   2 \n\nmove 5 from 1 to 2\n")
+            .err()
+            .unwrap();
+        assert!(error.message() == "Stack 1 only has 1 crates to move");
+        assert!(error.line_number() == Some(4) && error.column() == Some(6));
+    }
+}
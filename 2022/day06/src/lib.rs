@@ -0,0 +1,190 @@
+use aoc::{Generator, ParseError, Rng, Solution};
+use std::io::BufRead;
+
+/// Where the first run of each length of distinct characters ends in the datastream. There are
+/// only 128 ASCII characters, so there are no longer runs and this stays small however long the
+/// stream is.
+pub struct Runs {
+    ends: Vec<usize>,
+}
+
+impl Runs {
+    /// The offset of the first character after the first run of `length` distinct characters.
+    fn offset_after_marker(&self, length: usize) -> Option<usize> {
+        self.ends.get(length - 1).copied()
+    }
+}
+
+/// Find where the first run of each length of distinct characters ends. The stream is read a
+/// buffer at a time and only where each character was last seen is kept, so a stream of any
+/// length takes the same memory. Line breaks aren't part of the stream.
+pub fn find_runs(mut reader: impl BufRead) -> Result<Runs, ParseError> {
+    let mut ends = Vec::new();
+    // The offset after each character was last seen, and the start of the run of distinct
+    // characters that ends with the latest one.
+    let mut seen_until = [0; 128];
+    let mut run_start = 0;
+    let mut offset = 0;
+
+    while ends.len() < seen_until.len() {
+        let buffer = reader
+            .fill_buf()
+            .map_err(|e| ParseError::new(format!("Unable to read the input: {}", e)))?;
+        if buffer.is_empty() {
+            break;
+        }
+
+        let length = buffer.len();
+        for &byte in buffer {
+            if byte == b'\n' || byte == b'\r' {
+                continue;
+            }
+            if !byte.is_ascii() {
+                return Err(ParseError::new(format!(
+                    "Expected letters, found a non-ASCII character after {} characters",
+                    offset
+                )));
+            }
+
+            run_start = run_start.max(seen_until[byte as usize]);
+            offset += 1;
+            seen_until[byte as usize] = offset;
+
+            // Runs grow a character at a time, so a run longer than any before is one longer.
+            if offset - run_start > ends.len() {
+                ends.push(offset);
+            }
+        }
+        reader.consume(length);
+    }
+
+    Ok(Runs { ends })
+}
+
+/// Add a datastream of about `size` bytes, with a start-of-packet marker a third of the way in
+/// and, if `with_message`, a start-of-message marker two thirds of the way in. The stretches
+/// around the markers only have three letters each, so they can't make a marker on their own.
+fn generate_datastream(rng: &mut Rng, size: usize, with_message: bool, input: &mut String) {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    let mut add_distinct = |rng: &mut Rng, input: &mut String, count: usize| {
+        rng.shuffle(&mut letters);
+        input.extend(&letters[..count]);
+    };
+    let add_noise = |rng: &mut Rng, input: &mut String, until: usize| {
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut letters);
+        while input.len() < until {
+            input.push(*rng.choose(&letters[..3]).unwrap());
+        }
+    };
+
+    add_noise(rng, input, size / 3);
+    add_distinct(rng, input, 4);
+    add_noise(rng, input, size * 2 / 3);
+    if with_message {
+        add_distinct(rng, input, 14);
+    }
+    add_noise(rng, input, size.saturating_sub(1));
+    input.push('\n');
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Runs;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        find_runs(input.as_bytes())
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input, ParseError> {
+        find_runs(reader)
+    }
+
+    /// The offset of the end of the start-of-packet marker, if there is one.
+    fn part1(runs: &Self::Input) -> Option<usize> {
+        runs.offset_after_marker(4)
+    }
+
+    /// The offset of the end of the start-of-message marker, if there is one.
+    fn part2(runs: &Self::Input) -> Option<usize> {
+        runs.offset_after_marker(14)
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![
+            Generator::new(
+                "random",
+                "A datastream with markers a third and two thirds of the way in",
+                |rng, size, input| generate_datastream(rng, size, true, input),
+            ),
+            Generator::new(
+                "no-message",
+                "A datastream with a start-of-packet marker but no start-of-message marker",
+                |rng, size, input| generate_datastream(rng, size, false, input),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{find_runs, Day6};
+    use aoc::{Generated, Rng, Solution};
+    use std::io::BufReader;
+
+    /// A datastream of about `size` bytes of only three letters, with the markers at the end, and
+    /// where the markers end.
+    fn generated_input(size: u64) -> (BufReader<impl std::io::Read>, usize, usize) {
+        const PIECE: u64 = 4096;
+        let pieces = size / PIECE;
+        let mut rng = Rng::new(6);
+        let mut made = 0;
+        let reader = BufReader::new(Generated::new(pieces * PIECE + 18, move |piece| {
+            made += 1;
+            if made > pieces {
+                // Whatever came before, the x's start a new run.
+                piece.push_str("xxxxabcdefghijklm\n");
+                return;
+            }
+            for _ in 0..PIECE {
+                piece.push(*rng.choose(&['x', 'y', 'z']).unwrap());
+            }
+        }));
+        let length = (pieces * PIECE) as usize;
+        (reader, length + 7, length + 17)
+    }
+
+    #[test]
+    fn examples() {
+        let markers = |stream: &str| {
+            let runs = find_runs(stream.as_bytes()).unwrap();
+            (Day6::part1(&runs), Day6::part2(&runs))
+        };
+        assert!(markers("mjqjpqmgbljsphdztnvjfqwrcgsmlb") == (Some(7), Some(19)));
+        assert!(markers("bvwbjplbgvbhsrlpgdmjqwftvncz\n") == (Some(5), Some(23)));
+        assert!(markers("nppdvjthqldpwncqszvftbrmjlhg") == (Some(6), Some(23)));
+        assert!(markers("aaaa") == (None, None));
+    }
+
+    #[test]
+    fn large_inputs_take_little_memory() {
+        let (input, packet, message) = generated_input(1 << 22);
+        let (runs, memory) = aoc::memory::measure(|| find_runs(input).unwrap());
+        assert!(runs.offset_after_marker(4) == Some(packet));
+        assert!(runs.offset_after_marker(14) == Some(message));
+        assert!(memory.unwrap().peak_bytes < 64 * 1024);
+    }
+
+    /// Run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn multi_gigabyte_input() {
+        let (input, packet, message) = generated_input(4 << 30);
+        let runs = find_runs(input).unwrap();
+        assert!(runs.offset_after_marker(4) == Some(packet));
+        assert!(runs.offset_after_marker(14) == Some(message));
+    }
+}
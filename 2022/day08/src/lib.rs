@@ -0,0 +1,275 @@
+use aoc::{Alternative, Answer, Generator, ParseError, Part, Rng, Solution};
+use geometry::{Direction, Point};
+use std::collections::HashSet;
+
+type UnsignedPoint = Point;
+
+#[derive(Debug)]
+pub struct Grid {
+    grid: Vec<Vec<i8>>,
+}
+
+impl Grid {
+    fn new(grid: Vec<Vec<i8>>) -> Grid {
+        Grid { grid }
+    }
+
+    fn height(&self) -> usize {
+        self.grid.len()
+    }
+
+    fn width(&self) -> usize {
+        self.grid[0].len()
+    }
+
+    fn tree_height_at(&self, at: &UnsignedPoint) -> Option<i8> {
+        Some(self.grid[at.y as usize][at.x as usize])
+    }
+
+    fn scenic_score_at(&self, at: &UnsignedPoint) -> i32 {
+        let height = self.tree_height_at(at);
+
+        let mut scores = [0, 0, 0, 0];
+        for (i, d) in Direction::all().iter().cloned().enumerate() {
+            for pt in self.iter_points_from_point_to_edge_in_direction(at, d) {
+                if pt == *at {
+                    continue;
+                }
+
+                scores[i] += 1;
+
+                if self.tree_height_at(&pt) >= height {
+                    break;
+                }
+            }
+        }
+
+        let total_score = scores.iter().product();
+        total_score
+    }
+
+    fn iter_points(&self) -> Box<dyn Iterator<Item = UnsignedPoint>> {
+        let width = self.width() as i32;
+        Box::new(
+            (0..self.height())
+                .flat_map(move |y| (0..width).map(move |x| UnsignedPoint::new(x, y as i32))),
+        )
+    }
+
+    fn iter_points_from_edge_to_point_in_direction<'a, 'b>(
+        &'a self,
+        point: &'b UnsignedPoint,
+        direction: Direction,
+    ) -> Box<dyn Iterator<Item = UnsignedPoint> + 'a> {
+        let width = self.width() as i32;
+        let height = self.height() as i32;
+
+        let pt_x = point.x;
+        let pt_y = point.y;
+
+        let closure: Box<dyn Fn(i32) -> UnsignedPoint> = match direction {
+            Direction::North | Direction::South => Box::new(move |y| UnsignedPoint::new(pt_x, y)),
+            Direction::East | Direction::West => Box::new(move |x| UnsignedPoint::new(x, pt_y)),
+        };
+
+        match direction {
+            Direction::North => Box::new((0..pt_y).map(closure)),
+            Direction::East => Box::new((pt_x..width).rev().map(closure)),
+            Direction::South => Box::new((pt_y..height).rev().map(closure)),
+            Direction::West => Box::new((0..pt_x).map(closure)),
+        }
+    }
+
+    fn iter_points_from_point_to_edge_in_direction<'a, 'b>(
+        &'a self,
+        point: &'b UnsignedPoint,
+        direction: Direction,
+    ) -> Box<dyn Iterator<Item = UnsignedPoint> + 'a> {
+        let width = self.width() as i32;
+        let height = self.height() as i32;
+
+        let pt_x = point.x;
+        let pt_y = point.y;
+
+        let closure: Box<dyn Fn(i32) -> UnsignedPoint> = match direction {
+            Direction::North | Direction::South => Box::new(move |y| UnsignedPoint::new(pt_x, y)),
+            Direction::East | Direction::West => Box::new(move |x| UnsignedPoint::new(x, pt_y)),
+        };
+
+        match direction {
+            Direction::North => Box::new((0..pt_y).rev().map(closure)),
+            Direction::East => Box::new((pt_x..width).map(closure)),
+            Direction::South => Box::new((pt_y..height).map(closure)),
+            Direction::West => Box::new((0..pt_x).rev().map(closure)),
+        }
+    }
+
+    /// The number of trees visible from outside the grid, found by walking in from each end of
+    /// every row and column once, keeping track of the tallest tree so far.
+    fn count_visible_in_one_pass(&self) -> usize {
+        let (width, height) = (self.width(), self.height());
+        let mut visible = vec![vec![false; width]; height];
+
+        let mut look_along = |points: &mut dyn Iterator<Item = (usize, usize)>| {
+            let mut tallest_tree_height: i8 = -1;
+            for (x, y) in points {
+                let tree_height = self.grid[y][x];
+                if tree_height > tallest_tree_height {
+                    tallest_tree_height = tree_height;
+                    visible[y][x] = true;
+                }
+            }
+        };
+
+        for y in 0..height {
+            look_along(&mut (0..width).map(|x| (x, y)));
+            look_along(&mut (0..width).rev().map(|x| (x, y)));
+        }
+        for x in 0..width {
+            look_along(&mut (0..height).map(|y| (x, y)));
+            look_along(&mut (0..height).rev().map(|y| (x, y)));
+        }
+
+        visible.iter().flatten().filter(|&&v| v).count()
+    }
+
+    /// The grid of tree heights, with the visible trees in green.
+    fn picture_with_visible_set(&self, visible_trees: &HashSet<UnsignedPoint>) -> String {
+        let mut picture = String::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pt = UnsignedPoint::new(x as i32, y as i32);
+                let height = self.tree_height_at(&pt).unwrap();
+                if visible_trees.contains(&pt) {
+                    picture.push_str(&format!("\x1B[32m{}\x1B[0m", height));
+                } else {
+                    picture.push_str(&height.to_string());
+                }
+            }
+            picture.push('\n');
+        }
+        picture
+    }
+}
+
+/// Add a square grid of about `size` bytes, with each tree's height from `tree_height`.
+fn generate_grid(
+    rng: &mut Rng,
+    size: usize,
+    input: &mut String,
+    tree_height: impl Fn(&mut Rng) -> u64,
+) {
+    let side = ((size as f64).sqrt() as usize).max(1);
+    for _ in 0..side {
+        for _ in 0..side {
+            input.push_str(&tree_height(rng).to_string());
+        }
+        input.push('\n');
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Grid;
+    type Part1 = usize;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let grid = aoc::parse_lines(input, |l| {
+            l.chars()
+                .enumerate()
+                .map(|(i, c)| match c.to_digit(10) {
+                    Some(height) => Ok(height as i8),
+                    None => Err(ParseError::at(
+                        l,
+                        i + 1,
+                        format!("Expected a tree height, found '{}'", c),
+                    )),
+                })
+                .collect::<Result<Vec<i8>, ParseError>>()
+        })?;
+
+        let Some(first_row) = grid.first() else {
+            return Err(ParseError::new("Missing grid of trees"));
+        };
+        if let Some(i) = grid.iter().position(|row| row.len() != first_row.len()) {
+            return Err(ParseError::new(format!(
+                "Expected a row of {} trees, found {}",
+                first_row.len(),
+                grid[i].len()
+            ))
+            .with_line_number(i + 1));
+        }
+
+        Ok(Grid::new(grid))
+    }
+
+    /// The number of trees visible from outside the grid.
+    fn part1(grid: &Self::Input) -> usize {
+        let mut visible_trees: HashSet<UnsignedPoint> = HashSet::new();
+
+        for grid_pt in grid.iter_points() {
+            for direction in Direction::all().iter().cloned() {
+                let mut tallest_tree_height: i8 = -1;
+                for pt in grid.iter_points_from_edge_to_point_in_direction(&grid_pt, direction) {
+                    let tree_height = grid.tree_height_at(&pt).unwrap();
+                    if tree_height > tallest_tree_height {
+                        tallest_tree_height = tree_height;
+                        visible_trees.insert(pt);
+                    }
+                }
+            }
+        }
+
+        aoc::debug!("{}", grid.picture_with_visible_set(&visible_trees));
+
+        visible_trees.len()
+    }
+
+    /// The highest scenic score of any tree in the grid.
+    fn part2(grid: &Self::Input) -> i32 {
+        grid.iter_points()
+            .map(|pt| grid.scenic_score_at(&pt))
+            .max()
+            .unwrap_or(-1)
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![Alternative::new("single pass", Part::One, |grid| {
+            Answer::from(grid.count_visible_in_one_pass())
+        })]
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![
+            Generator::new("random", "Trees of random heights", |rng, size, input| {
+                generate_grid(rng, size, input, |rng| rng.between(0, 9))
+            }),
+            Generator::new(
+                "flat",
+                "Trees that are all the same height",
+                |rng, size, input| {
+                    let height = rng.between(0, 9);
+                    generate_grid(rng, size, input, |_| height)
+                },
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day8;
+    use aoc::Solution;
+
+    #[test]
+    fn generated_inputs_are_seeded_and_parse() {
+        for generator in Day8::generators() {
+            let input = generator.generate(2022, 2000);
+            assert!(input == generator.generate(2022, 2000));
+            assert!(input != generator.generate(2023, 2000));
+            assert!(Day8::parse(&input).is_ok());
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use aoc::{Generator, ParseError, Rng, Solution};
+use geometry::{Direction, Point};
+use std::collections::HashSet;
+
+type SignedPoint = Point;
+
+struct Rope {
+    nodes: Vec<SignedPoint>,
+}
+
+impl Rope {
+    fn with_length(length: usize) -> Rope {
+        Rope {
+            nodes: vec![SignedPoint::zero(); length],
+        }
+    }
+
+    fn move_head(&mut self, direction: Direction) {
+        if let Some(head) = self.nodes.first_mut() {
+            head.move_by_one_in(direction);
+            for i in 1..self.nodes.len() {
+                let first = self.nodes[i - 1].clone();
+                let second = self.nodes.get_mut(i).unwrap();
+
+                let distance = second.distance_to(&first);
+                if distance.0 == 2 {
+                    second.x += 1;
+                    if distance.1 < 0 {
+                        second.y -= 1;
+                    } else if distance.1 > 0 {
+                        second.y += 1;
+                    }
+                } else if distance.0 == -2 {
+                    second.x -= 1;
+                    if distance.1 < 0 {
+                        second.y -= 1;
+                    } else if distance.1 > 0 {
+                        second.y += 1;
+                    }
+                } else if distance.1 == 2 {
+                    second.y += 1;
+                    if distance.0 < 0 {
+                        second.x -= 1;
+                    } else if distance.0 > 0 {
+                        second.x += 1;
+                    }
+                } else if distance.1 == -2 {
+                    second.y -= 1;
+                    if distance.0 < 0 {
+                        second.x -= 1;
+                    } else if distance.0 > 0 {
+                        second.x += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Pull a rope of the given length through a series of head motions, and count the number of
+/// distinct points its tail visits.
+fn number_of_points_visited_by_tail(motions: &[(Direction, u8)], length: usize) -> usize {
+    let mut visited_points: HashSet<SignedPoint> = HashSet::new();
+    let mut rope = Rope::with_length(length);
+
+    for (direction, count) in motions {
+        for _ in 0..*count {
+            rope.move_head(*direction);
+            if let Some(last_node) = rope.nodes.last() {
+                visited_points.insert(last_node.clone());
+            }
+        }
+    }
+
+    visited_points.len()
+}
+
+/// Add about `size` bytes of motions of up to `longest` steps each.
+fn generate_motions(rng: &mut Rng, size: usize, longest: u64, input: &mut String) {
+    while input.len() < size {
+        let direction = rng.choose(&["U", "D", "L", "R"]).unwrap();
+        input.push_str(&format!("{} {}\n", direction, rng.between(1, longest)));
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<(Direction, u8)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        aoc::parse_lines(input, |line| {
+            let Some((direction_letter, count)) = line.split_once(' ') else {
+                return Err(ParseError::at(
+                    line,
+                    line.len() + 1,
+                    "Expected a direction and a count",
+                ));
+            };
+
+            let direction =
+                Direction::from_relative_direction(direction_letter).ok_or_else(|| {
+                    ParseError::at_token(
+                        line,
+                        direction_letter,
+                        format!("Expected U, D, L or R, found '{}'", direction_letter),
+                    )
+                })?;
+            let count = aoc::parse_number::<u8>(line, count)?;
+
+            Ok((direction, count))
+        })
+    }
+
+    /// The number of points the tail node of a two-node rope visited.
+    fn part1(motions: &Self::Input) -> usize {
+        number_of_points_visited_by_tail(motions, 2)
+    }
+
+    /// The number of points the tail node of a ten-node rope visited.
+    fn part2(motions: &Self::Input) -> usize {
+        number_of_points_visited_by_tail(motions, 10)
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![
+            Generator::new(
+                "random",
+                "Motions of up to 20 steps in random directions",
+                |rng, size, input| generate_motions(rng, size, 20, input),
+            ),
+            Generator::new(
+                "long",
+                "Motions of up to 255 steps, the most there can be",
+                |rng, size, input| generate_motions(rng, size, 255, input),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day9;
+    use aoc::Solution;
+
+    #[test]
+    fn generated_inputs_are_seeded_and_parse() {
+        for generator in Day9::generators() {
+            let input = generator.generate(2022, 2000);
+            assert!(input == generator.generate(2022, 2000));
+            assert!(input != generator.generate(2023, 2000));
+            assert!(Day9::parse(&input).is_ok());
+        }
+    }
+}
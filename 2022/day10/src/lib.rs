@@ -0,0 +1,190 @@
+use aoc::{Answer, Generator, ParseError, Rng, Solution};
+
+#[derive(Clone, Debug)]
+pub enum Instruction {
+    Noop,
+    AddX(i32),
+}
+
+impl Instruction {
+    fn number_of_cycles(&self) -> u32 {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::AddX(_) => 2,
+        }
+    }
+}
+
+struct Cpu<'a> {
+    instructions: Box<dyn Iterator<Item = Instruction> + 'a>,
+    current_instruction: Option<Instruction>,
+    cycle: u32,
+    x: i32,
+}
+
+impl<'a> Cpu<'a> {
+    fn new<T: Iterator<Item = Instruction> + 'a>(instructions: T) -> Cpu<'a> {
+        Cpu {
+            instructions: Box::new(instructions),
+            current_instruction: None,
+            cycle: 0,
+            x: 1,
+        }
+    }
+}
+
+impl Iterator for Cpu<'_> {
+    type Item = State;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_instruction.is_none() {
+            self.current_instruction = self.instructions.next();
+            self.cycle = 0;
+        }
+
+        if let Some(current_instruction) = &self.current_instruction {
+            let state = Some(State::from_cpu(self));
+            self.cycle += 1;
+
+            if self.cycle >= current_instruction.number_of_cycles() {
+                match current_instruction {
+                    Instruction::Noop => {}
+                    Instruction::AddX(value) => {
+                        self.x += value;
+                    }
+                }
+
+                self.current_instruction = self.instructions.next();
+                self.cycle = 0;
+            }
+
+            state
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct State {
+    x: i32,
+}
+
+impl State {
+    fn from_cpu(cpu: &Cpu) -> State {
+        State { x: cpu.x }
+    }
+}
+
+/// Add about `size` bytes of instructions. X stays between -20 and 40, on whichever side of zero
+/// pulls the sum of the signal strengths back towards zero, so the sum doesn't overflow however
+/// long the program is.
+fn generate_program(rng: &mut Rng, size: usize, input: &mut String) {
+    let mut x: i64 = 1;
+    let mut cycle: i64 = 0;
+    let mut signal_strengths: i64 = 0;
+
+    while input.len() < size {
+        let (cycles, next_x) = if rng.between(0, 2) == 0 {
+            input.push_str("noop\n");
+            (1, x)
+        } else {
+            let next_x = if signal_strengths > 0 {
+                rng.between(0, 20) as i64 - 20
+            } else {
+                rng.between(0, 40) as i64
+            };
+            input.push_str(&format!("addx {}\n", next_x - x));
+            (2, next_x)
+        };
+
+        for during in cycle + 1..=cycle + cycles {
+            if during % 40 == 20 {
+                signal_strengths += during * x;
+            }
+        }
+        cycle += cycles;
+        x = next_x;
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        aoc::parse_lines(input, |line| -> Result<Instruction, ParseError> {
+            match line.split_once(' ') {
+                Some(("addx", value)) => Ok(Instruction::AddX(aoc::parse_number(line, value)?)),
+                None if line == "noop" => Ok(Instruction::Noop),
+                _ => Err(ParseError::at(
+                    line,
+                    1,
+                    format!("Expected 'noop' or 'addx N', found '{}'", line),
+                )),
+            }
+        })
+    }
+
+    /// The sum of the signal strengths during the 20th, 60th, 100th, ... cycles.
+    fn part1(instructions: &Self::Input) -> i32 {
+        Cpu::new(instructions.iter().cloned())
+            .enumerate()
+            .map(|(i, cycle)| (i as i32 + 1, cycle.x))
+            .filter(|(cycle_number, _)| cycle_number % 40 == 20)
+            .map(|(cycle_number, x)| x * cycle_number)
+            .sum()
+    }
+
+    /// The image drawn on the CRT, one line per row of pixels.
+    fn part2(instructions: &Self::Input) -> Answer {
+        let mut crt = String::new();
+
+        for (i, cycle) in Cpu::new(instructions.iter().cloned()).enumerate() {
+            let x = cycle.x;
+
+            let i = i as i32;
+            if i > 0 && i % 40 == 0 {
+                crt.push('\n');
+            }
+            let horizontal_beam_position = i % 40;
+            if horizontal_beam_position == (x - 1)
+                || horizontal_beam_position == x
+                || horizontal_beam_position == (x + 1)
+            {
+                crt.push('#');
+            } else {
+                crt.push('.');
+            }
+        }
+
+        Answer::glyphs(&crt)
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![Generator::new(
+            "random",
+            "A program of noop and addx instructions",
+            generate_program,
+        )]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day10;
+    use aoc::Solution;
+
+    #[test]
+    fn generated_inputs_are_seeded_and_parse() {
+        for generator in Day10::generators() {
+            let input = generator.generate(2022, 2000);
+            assert!(input == generator.generate(2022, 2000));
+            assert!(input != generator.generate(2023, 2000));
+            assert!(Day10::parse(&input).is_ok());
+        }
+    }
+}
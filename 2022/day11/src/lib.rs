@@ -0,0 +1,688 @@
+use aoc::{Generator, ParseError, Rng, Section, Solution};
+use std::cell::RefCell;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Term {
+    Old,
+    Fixed(i64),
+}
+
+impl TryFrom<&str> for Term {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        if s == "old" {
+            Ok(Term::Old)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Ok(Term::Fixed(n))
+        } else {
+            Err(ParseError::new(format!(
+                "Expected 'old' or a number, found '{s}'"
+            )))
+        }
+    }
+}
+
+/// An operation like `new = old * 19`. The left-hand side of it is always `old`.
+#[derive(Clone, Debug)]
+struct Operation {
+    operator: Operator,
+    right: Term,
+}
+
+impl Operation {
+    fn perform(&self, item: &i64) -> i64 {
+        match self.operator {
+            Operator::Add => match self.right {
+                Term::Old => {
+                    let result = item + item;
+                    aoc::trace!("    Worry level increases by itself to {result}.");
+                    item + item
+                }
+                Term::Fixed(value) => {
+                    let result = item + value;
+                    aoc::trace!("    Worry level increases by {value} to {result}.");
+                    result
+                }
+            },
+            Operator::Sub => match self.right {
+                Term::Old => {
+                    let result = 0;
+                    aoc::trace!("    Worry level decreases by itself to {result}.");
+                    result
+                }
+                Term::Fixed(value) => {
+                    let result = item - value;
+                    aoc::trace!("    Worry level decreases by {value} to {result}.");
+                    result
+                }
+            },
+            Operator::Mul => match self.right {
+                Term::Old => {
+                    let result = item * item;
+                    aoc::trace!("    Worry level is multiplied by itself to {result}.");
+                    result
+                }
+                Term::Fixed(value) => {
+                    let result = item * value;
+                    aoc::trace!("    Worry level is multiplied by {value} to {result}.");
+                    result
+                }
+            },
+            Operator::Div => match self.right {
+                Term::Old => {
+                    let result = 1;
+                    aoc::trace!("    Worry level is divided by itself to {result}.");
+                    result
+                }
+                Term::Fixed(value) => {
+                    let result = item / value;
+                    aoc::trace!("    Worry level is divided by {value} to {result}.");
+                    result
+                }
+            },
+        }
+    }
+}
+
+impl Operation {
+    fn new(operator: Operator, right: Term) -> Operation {
+        Operation { operator, right }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl TryFrom<&str> for Operator {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "+" => Ok(Operator::Add),
+            "-" => Ok(Operator::Sub),
+            "*" => Ok(Operator::Mul),
+            "/" => Ok(Operator::Div),
+            _ => Err(ParseError::new(format!(
+                "Expected one of + - * /, found '{s}'"
+            ))),
+        }
+    }
+}
+
+struct InspectionReport {
+    target_monkey: usize,
+    item: i64,
+}
+
+impl InspectionReport {
+    fn new(target_monkey: usize, item: i64) -> InspectionReport {
+        InspectionReport {
+            target_monkey,
+            item,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    n: usize,
+    items: RefCell<Vec<i64>>,
+    operation: Operation,
+    test_divisor: i64,
+    target_monkey_if_true: usize,
+    target_monkey_if_false: usize,
+}
+
+impl Monkey {
+    fn take_item(&self, item: i64) {
+        self.items.borrow_mut().push(item);
+    }
+
+    fn inspect_items(
+        &self,
+        with_anxiety_easying: bool,
+        worry_modulus: i64,
+    ) -> Vec<InspectionReport> {
+        aoc::trace!("Monkey {}:", self.n);
+        let reports = self
+            .items
+            .borrow()
+            .iter()
+            .map(|item| self._inspect_item(item, with_anxiety_easying, worry_modulus))
+            .collect();
+
+        self.items.borrow_mut().clear();
+
+        reports
+    }
+
+    fn _inspect_item(
+        &self,
+        item: &i64,
+        should_ease_anxiety: bool,
+        worry_modulus: i64,
+    ) -> InspectionReport {
+        aoc::trace!("  Monkey inspects an item with a worry level of {}", item);
+        let mut modified_worry_level = self.operation.perform(item);
+
+        if should_ease_anxiety {
+            aoc::trace!(
+                "    Monkey gets bored with item. Worry level is divided by 3 to {}",
+                modified_worry_level
+            );
+            modified_worry_level /= 3;
+        } else {
+            // Every monkey's test divisor divides the modulus, so reducing worry levels by it
+            // keeps them small without changing where any item is thrown.
+            modified_worry_level %= worry_modulus;
+            aoc::trace!(
+                "    Monkey gets bored with item. Normalizing worry level to {}",
+                modified_worry_level
+            );
+        }
+
+        if modified_worry_level % self.test_divisor == 0 {
+            aoc::trace!(
+                "    Current worry level is divisible by {}",
+                self.test_divisor
+            );
+            aoc::trace!(
+                "    Item with worry level {} is thrown to {}",
+                modified_worry_level,
+                self.target_monkey_if_true
+            );
+            InspectionReport::new(self.target_monkey_if_true, modified_worry_level)
+        } else {
+            aoc::trace!(
+                "    Current worry level is not divisible by {}",
+                self.test_divisor
+            );
+            aoc::trace!(
+                "    Item with worry level {} is thrown to {}",
+                modified_worry_level,
+                self.target_monkey_if_false
+            );
+            InspectionReport::new(self.target_monkey_if_false, modified_worry_level)
+        }
+    }
+}
+
+struct MonkeyBuilder {
+    n: usize,
+    items: Vec<i64>,
+    operation: Option<Operation>,
+    test_divisor: Option<i64>,
+    target_monkey_if_true: Option<usize>,
+    target_monkey_if_false: Option<usize>,
+}
+
+impl MonkeyBuilder {
+    fn new(n: usize) -> MonkeyBuilder {
+        MonkeyBuilder {
+            n,
+            items: Vec::new(),
+            operation: None,
+            test_divisor: None,
+            target_monkey_if_true: None,
+            target_monkey_if_false: None,
+        }
+    }
+
+    fn build(self) -> Result<Monkey, ParseError> {
+        let n = self.n;
+        let missing = |what: &str| ParseError::new(format!("Monkey {} is missing its {}", n, what));
+        let operation = self.operation.ok_or_else(|| missing("operation"))?;
+        let test_divisor = self.test_divisor.ok_or_else(|| missing("test"))?;
+        let target_monkey_if_true = self
+            .target_monkey_if_true
+            .ok_or_else(|| missing("'If true' line"))?;
+        let target_monkey_if_false = self
+            .target_monkey_if_false
+            .ok_or_else(|| missing("'If false' line"))?;
+
+        Ok(Monkey {
+            n,
+            items: RefCell::new(self.items),
+            operation,
+            test_divisor,
+            target_monkey_if_true,
+            target_monkey_if_false,
+        })
+    }
+
+    fn items(mut self, items: Vec<i64>) -> MonkeyBuilder {
+        self.items = items;
+        self
+    }
+
+    fn operation(mut self, operation: Operation) -> MonkeyBuilder {
+        self.operation = Some(operation);
+        self
+    }
+
+    fn test_divisor(mut self, divisor: i64) -> MonkeyBuilder {
+        self.test_divisor = Some(divisor);
+        self
+    }
+
+    fn target_monkey_if_true(mut self, target_monkey: usize) -> MonkeyBuilder {
+        self.target_monkey_if_true = Some(target_monkey);
+        self
+    }
+
+    fn target_monkey_if_false(mut self, target_monkey: usize) -> MonkeyBuilder {
+        self.target_monkey_if_false = Some(target_monkey);
+        self
+    }
+}
+
+/// The product of every monkey's test divisor.
+fn worry_modulus(monkeys: &[Monkey]) -> i64 {
+    monkeys.iter().map(|m| m.test_divisor).product()
+}
+
+/// Parse the `Monkey N:` line that starts a monkey's description.
+fn parse_header(line: &str, expected_n: usize) -> Result<MonkeyBuilder, ParseError> {
+    let number: &str = aoc::scan!(line, "Monkey {}:")?;
+    let n = aoc::parse_number::<usize>(line, number)?;
+    if n != expected_n {
+        return Err(ParseError::at_token(
+            line,
+            number,
+            format!("Expected monkey {}", expected_n),
+        ));
+    }
+
+    Ok(MonkeyBuilder::new(n))
+}
+
+/// Parse one line of a monkey's description into the builder for the monkey being read.
+fn parse_line(line: &str, builder: MonkeyBuilder) -> Result<MonkeyBuilder, ParseError> {
+    if line.starts_with("Monkey") {
+        return Err(ParseError::at(
+            line,
+            1,
+            "Expected a blank line before the next monkey",
+        ));
+    }
+
+    let builder = if line.starts_with("  Starting items:") {
+        let items: &str = aoc::scan!(line, "  Starting items: {}")?;
+        let items = items
+            .split(", ")
+            .map(|s| aoc::parse_number::<i64>(line, s))
+            .collect::<Result<Vec<i64>, ParseError>>()?;
+        builder.items(items)
+    } else if line.starts_with("  Operation:") {
+        let (left, operator, right): (&str, &str, &str) =
+            aoc::scan!(line, "  Operation: new = {} {} {}")?;
+
+        if left != "old" {
+            return Err(ParseError::at_token(
+                line,
+                left,
+                format!("Expected 'old', found '{left}'"),
+            ));
+        }
+        let right_term = Term::try_from(right).map_err(|e| e.with_token(line, right))?;
+        let operator = Operator::try_from(operator).map_err(|e| e.with_token(line, operator))?;
+
+        builder.operation(Operation::new(operator, right_term))
+    } else if line.starts_with("  Test:") {
+        let divisor_token: &str = aoc::scan!(line, "  Test: divisible by {}")?;
+        let divisor = aoc::parse_number::<i64>(line, divisor_token)?;
+        if divisor == 0 {
+            return Err(ParseError::at_token(
+                line,
+                divisor_token,
+                "Can't test divisibility by 0",
+            ));
+        }
+        builder.test_divisor(divisor)
+    } else if line.starts_with("    If true:") {
+        builder.target_monkey_if_true(aoc::scan!(line, "    If true: throw to monkey {}")?)
+    } else if line.starts_with("    If false:") {
+        builder.target_monkey_if_false(aoc::scan!(line, "    If false: throw to monkey {}")?)
+    } else {
+        return Err(ParseError::at(
+            line,
+            1,
+            "Unexpected line in monkey description",
+        ));
+    };
+
+    Ok(builder)
+}
+
+/// Parse a monkey's description, which should be for monkey number `expected_n`.
+fn parse_monkey(section: &Section, expected_n: usize) -> Result<Monkey, ParseError> {
+    let mut lines = section.numbered_lines();
+
+    // Sections always have at least one line.
+    let (line_number, header) = lines.next().unwrap();
+    let builder = parse_header(header, expected_n).map_err(|e| e.with_line_number(line_number))?;
+
+    let builder = lines.try_fold(builder, |builder, (line_number, line)| {
+        parse_line(line, builder).map_err(|e| e.with_line_number(line_number))
+    })?;
+
+    builder
+        .build()
+        .map_err(|e| e.with_line_number(section.first_line_number()))
+}
+
+/// The test divisors generated monkeys are given. Their product, which part 2 keeps worry levels
+/// below, still fits in an `i64` when it's squared.
+const DIVISORS: [i64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+/// The most items a generated input has, so the product of part 1's inspection counts fits in a
+/// `u32`.
+const MOST_ITEMS: usize = 3000;
+
+/// A monkey made up by the generator, before it's written out.
+struct MadeUpMonkey {
+    items: Vec<i64>,
+    operator: char,
+    /// What the old worry level is added to or multiplied by, or `None` for itself.
+    operand: Option<i64>,
+    test_divisor: i64,
+    if_true: usize,
+    if_false: usize,
+}
+
+impl MadeUpMonkey {
+    fn worry(&self, item: i128) -> i128 {
+        let operand = self.operand.map_or(item, i128::from);
+        match self.operator {
+            '+' => item + operand,
+            _ => item * operand,
+        }
+    }
+}
+
+/// Whether 20 rounds of the monkeys throwing items, with worry levels easing as they do in part 1,
+/// keep every worry level within an `i64`.
+fn fits_part1(monkeys: &[MadeUpMonkey]) -> bool {
+    let mut items: Vec<Vec<i128>> = monkeys
+        .iter()
+        .map(|monkey| monkey.items.iter().map(|&item| item.into()).collect())
+        .collect();
+
+    for _ in 0..20 {
+        for (n, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[n]) {
+                let item = monkey.worry(item);
+                if item > i64::MAX.into() {
+                    return false;
+                }
+                let item = item / 3;
+                if item % i128::from(monkey.test_divisor) == 0 {
+                    items[monkey.if_true].push(item);
+                } else {
+                    items[monkey.if_false].push(item);
+                }
+            }
+        }
+    }
+
+    true
+}
+
+/// Make up two to eight monkeys holding about `size` bytes of items between them. Like in the
+/// real inputs, one monkey squares worry levels if there are at least three, one or two multiply
+/// them and the rest add to them. Nobody throws to the monkey that squares, and the ones that
+/// multiply throw to ones that add, so worry levels rarely overflow in part 1.
+fn make_up_monkeys(rng: &mut Rng, size: usize) -> Vec<MadeUpMonkey> {
+    let number_of_monkeys = (size / 200).clamp(2, DIVISORS.len());
+    let number_of_items =
+        (size.saturating_sub(number_of_monkeys * 150) / 4).clamp(number_of_monkeys, MOST_ITEMS);
+
+    let mut operations: Vec<(char, Option<i64>)> = Vec::new();
+    if number_of_monkeys >= 3 {
+        operations.push(('*', None));
+    }
+    for _ in 0..if number_of_monkeys >= 4 { 2 } else { 1 } {
+        operations.push(('*', Some(rng.between(2, 19) as i64)));
+    }
+    while operations.len() < number_of_monkeys {
+        operations.push(('+', Some(rng.between(1, 8) as i64)));
+    }
+    rng.shuffle(&mut operations);
+
+    let mut divisors = DIVISORS;
+    rng.shuffle(&mut divisors);
+
+    let mut monkeys: Vec<MadeUpMonkey> = Vec::new();
+    for (n, &(operator, operand)) in operations.iter().enumerate() {
+        let targets: Vec<usize> = (0..number_of_monkeys)
+            .filter(|&target| {
+                let (target_operator, target_operand) = operations[target];
+                target != n
+                    && target_operand.is_some()
+                    && (operator == '+' || target_operator == '+')
+            })
+            .collect();
+        let if_true = *rng.choose(&targets).unwrap();
+        let others: Vec<usize> = targets.into_iter().filter(|&t| t != if_true).collect();
+        let if_false = rng.choose(&others).copied().unwrap_or(if_true);
+
+        monkeys.push(MadeUpMonkey {
+            items: vec![rng.between(50, 99) as i64],
+            operator,
+            operand,
+            test_divisor: divisors[n],
+            if_true,
+            if_false,
+        });
+    }
+    for _ in number_of_monkeys..number_of_items {
+        let n = rng.between(0, number_of_monkeys as u64 - 1) as usize;
+        monkeys[n].items.push(rng.between(50, 99) as i64);
+    }
+
+    monkeys
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let monkeys = aoc::sections(input)
+            .enumerate()
+            .map(|(n, section)| parse_monkey(&section, n))
+            .collect::<Result<Vec<Monkey>, ParseError>>()?;
+        if monkeys.len() < 2 {
+            return Err(ParseError::new(format!(
+                "Expected at least two monkeys, found {}",
+                monkeys.len()
+            )));
+        }
+
+        for monkey in &monkeys {
+            for target in [monkey.target_monkey_if_true, monkey.target_monkey_if_false] {
+                if target >= monkeys.len() {
+                    return Err(ParseError::new(format!(
+                        "Monkey {} throws to monkey {}, which doesn't exist",
+                        monkey.n, target
+                    )));
+                }
+            }
+        }
+
+        Ok(monkeys)
+    }
+
+    /// The level of monkey business after 20 rounds, with worry levels easing after each
+    /// inspection.
+    fn part1(monkeys: &Self::Input) -> u32 {
+        let part1_monkeys = monkeys.clone();
+        let worry_modulus = worry_modulus(monkeys);
+        let mut part1_monkey_inspection_counts: Vec<u32> = vec![0; part1_monkeys.len()];
+
+        for round in 1..=20 {
+            aoc::debug!("----- Round {round} -----");
+
+            for monkey in &part1_monkeys {
+                let reports = monkey.inspect_items(true, worry_modulus);
+                part1_monkey_inspection_counts[monkey.n] += reports.len() as u32;
+
+                for r in reports {
+                    part1_monkeys[r.target_monkey].take_item(r.item);
+                }
+            }
+
+            aoc::debug!(
+                "After round {round}, the monkeys are holding items with these worry levels:"
+            );
+            for monkey in &part1_monkeys {
+                aoc::debug!(
+                    "Monkey {}: {}",
+                    monkey.n,
+                    monkey
+                        .items
+                        .borrow()
+                        .iter()
+                        .map(|i| i.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                );
+            }
+        }
+
+        aoc::debug!("----- Final Counts -----");
+        for (i, c) in part1_monkey_inspection_counts.iter().enumerate() {
+            aoc::debug!("Monkey {i} inspected items {c} times.");
+        }
+
+        let mut part1_sorted_counts = part1_monkey_inspection_counts.clone();
+        part1_sorted_counts.sort_by(|a, b| b.cmp(a));
+        part1_sorted_counts[0] * part1_sorted_counts[1]
+    }
+
+    /// The level of monkey business after 10,000 rounds, with worry levels never easing.
+    fn part2(monkeys: &Self::Input) -> u64 {
+        let part2_monkeys = monkeys.clone();
+        let worry_modulus = worry_modulus(monkeys);
+        let mut part2_monkey_inspection_counts: Vec<u64> = vec![0; part2_monkeys.len()];
+        for round in 1..=10000 {
+            aoc::trace!("----- Round {round} -----");
+
+            for monkey in &part2_monkeys {
+                let reports = monkey.inspect_items(false, worry_modulus);
+                part2_monkey_inspection_counts[monkey.n] += reports.len() as u64;
+
+                for r in reports {
+                    part2_monkeys[r.target_monkey].take_item(r.item);
+                }
+            }
+
+            // The same rounds the puzzle shows the counts after.
+            if round == 1 || round == 20 || round % 1000 == 0 {
+                aoc::debug!("== After round {round} ==");
+                for (i, c) in part2_monkey_inspection_counts.iter().enumerate() {
+                    aoc::debug!("Monkey {i} inspected items {c} times.");
+                }
+            }
+        }
+
+        let mut part2_sorted_counts = part2_monkey_inspection_counts.clone();
+        part2_sorted_counts.sort_by(|a, b| b.cmp(a));
+        part2_sorted_counts[0] * part2_sorted_counts[1]
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![Generator::new(
+            "random",
+            "Two to eight monkeys, like the real ones, with up to 3000 items",
+            |rng, size, input| {
+                let monkeys = loop {
+                    let monkeys = make_up_monkeys(rng, size);
+                    if fits_part1(&monkeys) {
+                        break monkeys;
+                    }
+                };
+
+                for (n, monkey) in monkeys.iter().enumerate() {
+                    let items: Vec<String> = monkey.items.iter().map(|i| i.to_string()).collect();
+                    let operand = monkey
+                        .operand
+                        .map_or("old".to_string(), |operand| operand.to_string());
+                    if n > 0 {
+                        input.push('\n');
+                    }
+                    input.push_str(&format!(
+                        "Monkey {}:\n  Starting items: {}\n  Operation: new = old {} {}\n  \
+                         Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                         If false: throw to monkey {}\n",
+                        n,
+                        items.join(", "),
+                        monkey.operator,
+                        operand,
+                        monkey.test_divisor,
+                        monkey.if_true,
+                        monkey.if_false
+                    ));
+                }
+            },
+        )]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day11;
+    use aoc::Solution;
+
+    const MONKEYS: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+
+    fn parse_error(input: &str) -> String {
+        Day11::parse(input).err().unwrap().message().to_string()
+    }
+
+    #[test]
+    fn generated_inputs_are_seeded_and_parse() {
+        for generator in Day11::generators() {
+            let input = generator.generate(2022, 2000);
+            assert!(input == generator.generate(2022, 2000));
+            assert!(input != generator.generate(2023, 2000));
+            assert!(Day11::parse(&input).is_ok());
+        }
+    }
+
+    #[test]
+    fn malformed_monkeys_are_rejected() {
+        assert!(Day11::parse(MONKEYS).is_ok());
+        assert!(parse_error("") == "Expected at least two monkeys, found 0");
+        assert!(parse_error(&MONKEYS[..MONKEYS.find("\n\n").unwrap()]).contains("two monkeys"));
+        assert!(
+            parse_error(&MONKEYS.replacen("old * 19", "3 * old", 1)) == "Expected 'old', found '3'"
+        );
+        assert!(parse_error(&MONKEYS.replacen("by 23", "by 0", 1)).contains("by 0"));
+        let without_if_true = MONKEYS.replacen("    If true: throw to monkey 1\n", "", 1);
+        assert!(parse_error(&without_if_true) == "Monkey 0 is missing its 'If true' line");
+    }
+}
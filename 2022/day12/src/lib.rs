@@ -0,0 +1,399 @@
+use aoc::{Alternative, Answer, Generator, ParseError, Part, Rng, Solution};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+trait Elevation {
+    fn elevation(&self) -> Option<u32>;
+}
+
+impl Elevation for char {
+    fn elevation(&self) -> Option<u32> {
+        let value = match self {
+            'S' => 'a',
+            'E' => 'z',
+            'a'..='z' => *self,
+            _ => return None,
+        };
+
+        Some((value as u32) - 'a' as u32)
+    }
+}
+
+#[derive(Debug)]
+struct Square {
+    symbol: char,
+    up: Option<usize>,
+    down: Option<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl Square {
+    fn new(symbol: char) -> Square {
+        Square {
+            symbol,
+            up: None,
+            down: None,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn elevation(&self) -> u32 {
+        self.symbol.elevation().unwrap()
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+struct State {
+    node: usize,
+    cost: u32,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// An implemetation of Dijkstra's algorithm to find the shortest path from start to end and return
+/// its length.
+fn find_length_of_shortest_path(squares: &[Square], start: usize, end: usize) -> Option<u32> {
+    let mut distances: Vec<_> = (0..squares.len()).map(|_| u32::MAX).collect();
+    let mut heap = BinaryHeap::new();
+
+    // Initial state
+    distances[start] = 0;
+    heap.push(State {
+        node: start,
+        cost: 0,
+    });
+
+    while let Some(State { node, cost }) = heap.pop() {
+        if node == end {
+            return Some(cost);
+        }
+
+        if cost > distances[node] {
+            continue;
+        }
+
+        let square = &squares[node];
+        for edge in &[square.up, square.right, square.down, square.left] {
+            if edge.is_none() {
+                continue;
+            }
+
+            let edge = edge.unwrap();
+
+            let next_cost = cost + 1;
+            let next = State {
+                node: edge,
+                cost: next_cost,
+            };
+            if next.cost < distances[edge] {
+                heap.push(next);
+                distances[edge] = next_cost;
+            }
+        }
+    }
+
+    None
+}
+
+/// The length of the shortest path from any of the starts to the end, found with a breadth-first
+/// search. Every step costs the same, so squares are reached in order of distance without needing
+/// Dijkstra's priority queue.
+fn breadth_first_length_of_shortest_path(
+    squares: &[Square],
+    starts: impl IntoIterator<Item = usize>,
+    end: usize,
+) -> Option<u32> {
+    let mut distances: Vec<Option<u32>> = vec![None; squares.len()];
+    let mut queue = VecDeque::new();
+    for start in starts {
+        distances[start] = Some(0);
+        queue.push_back(start);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[node]?;
+        if node == end {
+            return Some(distance);
+        }
+
+        let square = &squares[node];
+        for edge in [square.up, square.right, square.down, square.left]
+            .into_iter()
+            .flatten()
+        {
+            if distances[edge].is_none() {
+                distances[edge] = Some(distance + 1);
+                queue.push_back(edge);
+            }
+        }
+    }
+
+    None
+}
+
+pub struct HeightMap {
+    squares: Vec<Square>,
+    start: usize,
+    end: usize,
+}
+
+/// Make up a height map of about `size` bytes, at least 60 squares wide. The land rises steadily
+/// towards E with dips here and there, and S is somewhere at the lowest elevation. If `reachable`,
+/// maps are made until there's a path from S to E, and otherwise the squares around E are too low
+/// to climb to it from.
+fn generate_height_map(rng: &mut Rng, size: usize, reachable: bool, input: &mut String) {
+    let width = (((size * 3) as f64).sqrt() as usize).max(60);
+    let height = (size / (width + 1)).max(3);
+
+    loop {
+        let (end_x, end_y) = (
+            rng.between(0, width as u64 - 1) as f64,
+            rng.between(0, height as u64 - 1) as f64,
+        );
+        let distance = |x: usize, y: usize| (x as f64 - end_x).hypot(y as f64 - end_y);
+        // The far corner is at elevation a, and it's always far enough away that the land never
+        // rises by more than one between neighbouring squares.
+        let farthest = [
+            (0, 0),
+            (width - 1, 0),
+            (0, height - 1),
+            (width - 1, height - 1),
+        ]
+        .into_iter()
+        .map(|(x, y)| distance(x, y))
+        .fold(0.0, f64::max);
+
+        let mut rows: Vec<Vec<char>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let mut elevation = (25.0 * (1.0 - distance(x, y) / farthest)) as u64;
+                        if rng.between(0, 5) == 0 {
+                            elevation = elevation.saturating_sub(rng.between(1, 3));
+                        }
+                        (b'a' + elevation as u8) as char
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (end_x, end_y) = (end_x as usize, end_y as usize);
+        rows[end_y][end_x] = 'E';
+        if !reachable {
+            let neighbours = [(0, 1), (2, 1), (1, 0), (1, 2)]
+                .map(|(dx, dy)| ((end_x + dx).checked_sub(1), (end_y + dy).checked_sub(1)));
+            for (x, y) in neighbours {
+                if let Some(square) = x.zip(y).and_then(|(x, y)| rows.get_mut(y)?.get_mut(x)) {
+                    *square = (*square).min('x');
+                }
+            }
+        }
+
+        let lowest: Vec<(usize, usize)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| rows[y][x] == 'a')
+            .collect();
+        let (start_x, start_y) = *rng.choose(&lowest).unwrap();
+        rows[start_y][start_x] = 'S';
+
+        let map: String = rows
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect();
+        let solvable = Day12::parse(&map).ok().and_then(|map| {
+            breadth_first_length_of_shortest_path(&map.squares, [map.start], map.end)
+        });
+        if solvable.is_some() || !reachable {
+            input.push_str(&map);
+            return;
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = HeightMap;
+    type Part1 = Option<u32>;
+    type Part2 = Option<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let character_grid = aoc::parse_lines(input, |l| {
+            match l.char_indices().find(|(_, c)| c.elevation().is_none()) {
+                Some((column, c)) => Err(ParseError::at(
+                    l,
+                    column + 1,
+                    format!("Expected an elevation from a to z, S or E, found '{}'", c),
+                )),
+                None => Ok(l.chars().collect::<Vec<char>>()),
+            }
+        })?;
+
+        let Some(first_row) = character_grid.first() else {
+            return Err(ParseError::new("Missing height map"));
+        };
+
+        // Assume a square grid
+        let width = first_row.len();
+        let height = character_grid.len();
+
+        if let Some(y) = character_grid.iter().position(|row| row.len() != width) {
+            return Err(ParseError::new(format!(
+                "Expected a row of {} squares, found {}",
+                width,
+                character_grid[y].len()
+            ))
+            .with_line_number(y + 1));
+        }
+
+        let index_into_squares_array = |x: usize, y: usize| -> usize { y * width + x };
+
+        let mut start: Option<usize> = None;
+        let mut end: Option<usize> = None;
+        let mut squares: Vec<Square> = Vec::new();
+
+        for (y, row) in character_grid.iter().enumerate() {
+            for (x, symbol) in row.iter().cloned().enumerate() {
+                match symbol {
+                    'S' => start = Some(index_into_squares_array(x, y)),
+                    'E' => end = Some(index_into_squares_array(x, y)),
+                    _ => {}
+                }
+
+                let elevation = symbol.elevation().unwrap();
+                let mut square = Square::new(symbol);
+
+                if y > 0 {
+                    let up_elevation = character_grid[y - 1][x].elevation().unwrap();
+                    if up_elevation <= elevation + 1 {
+                        square.up = Some(index_into_squares_array(x, y - 1));
+                    }
+                }
+
+                if y < height - 1 {
+                    let down_elevation = character_grid[y + 1][x].elevation().unwrap();
+                    if down_elevation <= elevation + 1 {
+                        square.down = Some(index_into_squares_array(x, y + 1));
+                    }
+                }
+
+                if x > 0 {
+                    let left_elevation = character_grid[y][x - 1].elevation().unwrap();
+                    if left_elevation <= elevation + 1 {
+                        square.left = Some(index_into_squares_array(x - 1, y));
+                    }
+                }
+
+                if x < width - 1 {
+                    let right_elevation = character_grid[y][x + 1].elevation().unwrap();
+                    if right_elevation <= elevation + 1 {
+                        square.right = Some(index_into_squares_array(x + 1, y));
+                    }
+                }
+
+                squares.push(square);
+            }
+        }
+
+        let Some(start) = start else {
+            return Err(ParseError::new("Missing start square 'S'"));
+        };
+        let Some(end) = end else {
+            return Err(ParseError::new("Missing end square 'E'"));
+        };
+
+        Ok(HeightMap {
+            squares,
+            start,
+            end,
+        })
+    }
+
+    /// The length of the shortest path from the start to the location with the best signal, if
+    /// it can be reached.
+    fn part1(map: &Self::Input) -> Option<u32> {
+        find_length_of_shortest_path(&map.squares, map.start, map.end)
+    }
+
+    /// The length of the shortest path from any square at the lowest elevation to the location
+    /// with the best signal, if it can be reached from any of them.
+    fn part2(map: &Self::Input) -> Option<u32> {
+        map.squares
+            .iter()
+            .enumerate()
+            .filter(|(_, sq)| sq.elevation() == 0)
+            .filter_map(|(i, _)| find_length_of_shortest_path(&map.squares, i, map.end))
+            .min()
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative::new("breadth-first", Part::One, |map| {
+                Answer::from(breadth_first_length_of_shortest_path(
+                    &map.squares,
+                    [map.start],
+                    map.end,
+                ))
+            }),
+            // Searching from every lowest square at once, rather than one after another.
+            Alternative::new("breadth-first", Part::Two, |map| {
+                let lowest = (0..map.squares.len()).filter(|&i| map.squares[i].elevation() == 0);
+                Answer::from(breadth_first_length_of_shortest_path(
+                    &map.squares,
+                    lowest,
+                    map.end,
+                ))
+            }),
+        ]
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![
+            Generator::new(
+                "random",
+                "Land rising towards E, with a path to it from S",
+                |rng, size, input| generate_height_map(rng, size, true, input),
+            ),
+            Generator::new(
+                "unreachable",
+                "Land rising towards E, which is too high to climb to",
+                |rng, size, input| generate_height_map(rng, size, false, input),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day12;
+    use aoc::Solution;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example() {
+        let map = Day12::parse(EXAMPLE).unwrap();
+        assert!(Day12::part1(&map) == Some(31) && Day12::part2(&map) == Some(29));
+    }
+
+    #[test]
+    fn unreachable_end_has_no_answer() {
+        let map = Day12::parse("SabE\n").unwrap();
+        assert!(Day12::part1(&map).is_none() && Day12::part2(&map).is_none());
+    }
+}
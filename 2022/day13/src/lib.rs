@@ -0,0 +1,470 @@
+use aoc::{Generator, ParseError, Rng, Solution};
+use std::{cmp, fmt};
+
+pub type Pair = (Packet, Packet);
+type Int = u32;
+
+#[derive(Clone, Eq, PartialEq)]
+enum Datum {
+    Int(Int),
+    List(Vec<Datum>),
+}
+
+impl Datum {
+    fn is_int(&self) -> bool {
+        matches!(self, Datum::Int(_))
+    }
+
+    fn list_mut(&mut self) -> Option<&mut Vec<Datum>> {
+        match self {
+            Datum::List(ref mut list) => Some(list),
+            _ => None,
+        }
+    }
+}
+
+impl cmp::PartialOrd for Datum {
+    fn partial_cmp(&self, other: &Datum) -> Option<cmp::Ordering> {
+        aoc::trace!("  Compare {} vs {}", self, other);
+        match (self, other) {
+            (Datum::Int(self_value), Datum::Int(other_value)) => {
+                self_value.partial_cmp(other_value)
+            }
+            (Datum::Int(_), Datum::List(_)) => {
+                let self_list = Datum::List(vec![self.clone()]);
+                self_list.partial_cmp(other)
+            }
+            (Datum::List(_), Datum::Int(_)) => {
+                let other_list = Datum::List(vec![other.clone()]);
+                self.partial_cmp(&other_list)
+            }
+            (Datum::List(self_list), Datum::List(other_list)) => self_list.partial_cmp(other_list),
+        }
+    }
+}
+
+impl fmt::Debug for Datum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Datum::Int(value) => write!(f, "Int({})", value),
+            Datum::List(list) => {
+                let strings: Vec<String> = list.iter().map(|d| format!("{}", d)).collect();
+                write!(f, "[{}]", strings.join(","))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Datum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Datum::Int(value) => write!(f, "{}", value),
+            Datum::List(list) => {
+                let strings: Vec<String> = list.iter().map(|d| format!("{}", d)).collect();
+                write!(f, "[{}]", strings.join(","))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Packet(Datum);
+
+/// Pop the item on top of the parsing stack and push it onto the end of the list below it.
+fn push_top_item_into_list(
+    parsing_stack: &mut Vec<Datum>,
+    line: &str,
+    column: usize,
+) -> Result<(), ParseError> {
+    let top_item = parsing_stack
+        .pop()
+        .ok_or_else(|| ParseError::at(line, column, "Found a list item outside of any list"))?;
+    parsing_stack
+        .last_mut()
+        .and_then(|d| d.list_mut())
+        .ok_or_else(|| ParseError::at(line, column, "Found a list item outside of any list"))?
+        .push(top_item);
+    Ok(())
+}
+
+impl TryFrom<&str> for Packet {
+    type Error = ParseError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let mut parsing_stack = Vec::new();
+        let mut has_trailing_list_item = false;
+
+        for (i, c) in line.char_indices() {
+            let column = i + 1;
+            match c {
+                '[' => parsing_stack.push(Datum::List(Vec::new())),
+                ']' => {
+                    let top_item = parsing_stack.last().ok_or_else(|| {
+                        ParseError::at(line, column, "Found ']' with no open list")
+                    })?;
+
+                    if top_item.is_int() || has_trailing_list_item {
+                        push_top_item_into_list(&mut parsing_stack, line, column)?;
+                    }
+
+                    has_trailing_list_item = true;
+                }
+                c if c.is_ascii_digit() => {
+                    let c_digit: Int = c.to_digit(10).unwrap();
+                    match parsing_stack.last_mut() {
+                        Some(Datum::Int(accumulated_int)) => {
+                            *accumulated_int = accumulated_int
+                                .checked_mul(10)
+                                .and_then(|int| int.checked_add(c_digit))
+                                .ok_or_else(|| {
+                                    ParseError::at(line, column, "Number is too large")
+                                })?;
+                        }
+                        _ => parsing_stack.push(Datum::Int(c_digit)),
+                    }
+                }
+                ',' => {
+                    push_top_item_into_list(&mut parsing_stack, line, column)?;
+                    has_trailing_list_item = false;
+                }
+                _ => {
+                    return Err(ParseError::at(
+                        line,
+                        column,
+                        format!("Invalid character '{}'", c),
+                    ))
+                }
+            }
+        }
+
+        let end_column = line.len() + 1;
+
+        if has_trailing_list_item && parsing_stack.len() > 1 {
+            push_top_item_into_list(&mut parsing_stack, line, end_column)?;
+        }
+
+        if parsing_stack.len() != 1 {
+            return Err(ParseError::at(line, end_column, "Expected ']'"));
+        }
+
+        let top_item = parsing_stack.pop().unwrap();
+        let packet = Packet(top_item);
+
+        if format!("{}", packet) != line {
+            return Err(ParseError::at(line, 1, "Packet is not a well-formed list"));
+        }
+
+        Ok(packet)
+    }
+}
+
+impl cmp::Ord for Packet {
+    fn cmp(&self, other: &Packet) -> cmp::Ordering {
+        aoc::trace!("Compare {} vs {}", self.0, other.0);
+
+        let ordering = self.0.partial_cmp(&other.0);
+        aoc::trace!("  -> {:?}", ordering);
+
+        ordering.unwrap()
+    }
+}
+
+impl cmp::PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Packet) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Debug for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The packets part 2 adds, which the real inputs never have themselves.
+const DIVIDER_PACKETS: [&str; 2] = ["[[2]]", "[[6]]"];
+
+/// Add a pair of packets made by `generate`, after a blank line if it isn't the first, making
+/// others in place of any that are the same as a divider packet.
+fn generate_pair(rng: &mut Rng, input: &mut String, generate: impl Fn(&mut Rng, &mut String)) {
+    if !input.is_empty() {
+        input.push('\n');
+    }
+    for _ in 0..2 {
+        let mut packet = String::new();
+        while packet.is_empty() || DIVIDER_PACKETS.contains(&packet.as_str()) {
+            packet.clear();
+            generate(rng, &mut packet);
+        }
+        input.push_str(&packet);
+        input.push('\n');
+    }
+}
+
+/// Add a packet with up to five items in each list, nested up to `depth` lists deep.
+fn generate_packet(rng: &mut Rng, depth: u64, input: &mut String) {
+    input.push('[');
+    for i in 0..rng.between(0, 5) {
+        if i > 0 {
+            input.push(',');
+        }
+        if depth > 1 && rng.between(0, 2) == 0 {
+            generate_packet(rng, depth - 1, input);
+        } else {
+            input.push_str(&rng.between(0, 10).to_string());
+        }
+    }
+    input.push(']');
+}
+
+/// Add a packet nested exactly `depth` lists deep, with a number beside some of the lists.
+fn generate_deep_packet(rng: &mut Rng, depth: u64, input: &mut String) {
+    // How each list ends, from the outermost in.
+    let mut endings = Vec::new();
+    for _ in 0..depth {
+        input.push('[');
+        match rng.between(0, 5) {
+            0 => input.push_str(&format!("{},", rng.between(0, 10))),
+            1 => {
+                endings.push(format!(",{}]", rng.between(0, 10)));
+                continue;
+            }
+            _ => (),
+        }
+        endings.push("]".to_string());
+    }
+
+    input.push_str(&rng.between(0, 10).to_string());
+    for ending in endings.iter().rev() {
+        input.push_str(ending);
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Vec<Pair>, Vec<Packet>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut pairs: Vec<Pair> = Vec::new();
+        let mut completed_packets: Vec<Packet> = Vec::new();
+
+        for section in aoc::sections(input) {
+            let packets = section.parse_lines(|line| Packet::try_from(line))?;
+            for packet in &packets {
+                aoc::trace!("Completed packet! {}", packet);
+            }
+
+            let [left, right] = <[Packet; 2]>::try_from(packets).map_err(|_| {
+                ParseError::new("Expected a pair of packets")
+                    .with_line_number(section.first_line_number())
+            })?;
+
+            completed_packets.push(left.clone());
+            completed_packets.push(right.clone());
+            pairs.push((left, right));
+        }
+
+        Ok((pairs, completed_packets))
+    }
+
+    /// The sum of the indices of the pairs that are in the right order.
+    fn part1(input: &Self::Input) -> usize {
+        let (pairs, _) = input;
+
+        let pairs_in_right_order = pairs
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i + 1, p))
+            .filter(|(_, pair)| {
+                if let Some(ordering) = pair.0.partial_cmp(&pair.1) {
+                    ordering == cmp::Ordering::Less
+                } else {
+                    false
+                }
+            })
+            .collect::<Vec<(usize, &Pair)>>();
+
+        aoc::debug!("----- Pairs in the Right Order -----");
+        for (i, p) in &pairs_in_right_order {
+            aoc::debug!("{} ->\n  {}\n  {}", i, p.0, p.1);
+        }
+        aoc::debug!("{} pairs", pairs.len());
+
+        pairs_in_right_order.iter().map(|(i, _)| i).sum::<usize>()
+    }
+
+    /// The product of the indexes of the divider packets, after sorting all the packets.
+    fn part2(input: &Self::Input) -> usize {
+        let (_, packets) = input;
+
+        let divider_a = Packet(Datum::List(vec![Datum::List(vec![Datum::Int(2)])]));
+        let divider_b = Packet(Datum::List(vec![Datum::List(vec![Datum::Int(6)])]));
+
+        let mut completed_packets = packets.clone();
+        completed_packets.push(divider_a.clone());
+        completed_packets.push(divider_b.clone());
+        completed_packets.sort();
+
+        for packet in &completed_packets {
+            aoc::trace!("Sorted packet {}", packet);
+        }
+
+        completed_packets
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| **p == divider_a || **p == divider_b)
+            .map(|(i, _)| i + 1)
+            .product()
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![
+            Generator::new(
+                "random",
+                "Pairs of packets nested up to four lists deep",
+                |rng, size, input| {
+                    while input.len() < size {
+                        generate_pair(rng, input, |rng, packet| generate_packet(rng, 4, packet));
+                    }
+                },
+            ),
+            Generator::new(
+                "deep",
+                "Two pairs of packets nested about a list deep for every 12 bytes",
+                |rng, size, input| {
+                    let depth = (size as u64 / 12).max(1);
+                    for _ in 0..2 {
+                        generate_pair(rng, input, |rng, packet| {
+                            let packet_depth = rng.between(depth / 2, depth);
+                            generate_deep_packet(rng, packet_depth, packet)
+                        });
+                    }
+                },
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Datum, Packet};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn shorter_vecs_are_less() {
+        assert!(vec![].partial_cmp(&vec![3]).unwrap() == Ordering::Less);
+        assert!(vec![1, 2, 3].partial_cmp(&vec![1, 2, 3, 4]).unwrap() == Ordering::Less);
+    }
+
+    #[test]
+    fn single_empty_list() {
+        let packet = Packet::try_from("[]").unwrap();
+        assert!(packet == Packet(Datum::List(vec![])));
+    }
+
+    #[test]
+    fn three_int_list() {
+        let packet = Packet::try_from("[1,2,3]").unwrap();
+        assert!(
+            packet
+                == Packet(Datum::List(vec![
+                    Datum::Int(1),
+                    Datum::Int(2),
+                    Datum::Int(3),
+                ])),
+        );
+    }
+
+    #[test]
+    fn one_embedded_empty_list() {
+        let packet = Packet::try_from("[1,2,[],3,4]").unwrap();
+        assert!(
+            packet
+                == Packet(Datum::List(vec![
+                    Datum::Int(1),
+                    Datum::Int(2),
+                    Datum::List(vec![]),
+                    Datum::Int(3),
+                    Datum::Int(4),
+                ])),
+        );
+    }
+
+    #[test]
+    fn numbers_too_large_are_errors() {
+        let error = Packet::try_from("[1,99999999999]").err().unwrap();
+        assert!(error.message() == "Number is too large" && error.column() == Some(13));
+    }
+
+    #[test]
+    fn double_embedded_list() {
+        let packet = Packet::try_from("[[9,[8,7,8],[]]]").unwrap();
+        assert!(
+            packet
+                == Packet(Datum::List(vec![Datum::List(vec![
+                    Datum::Int(9),
+                    Datum::List(vec![Datum::Int(8), Datum::Int(7), Datum::Int(8)]),
+                    Datum::List(vec![])
+                ])]))
+        );
+    }
+
+    #[test]
+    fn first_input_packet() {
+        let packet = Packet::try_from("[[[7,8],5],[[9,[8,7,8],[],[2,4,10,10],[2,10,8,3,3]],[],[[6,1,10],[],3,6],[3]],[],[4],[3,0,1,10]]").unwrap();
+        assert!(
+            packet
+                == Packet(Datum::List(vec![
+                    Datum::List(vec![
+                        Datum::List(vec![Datum::Int(7), Datum::Int(8)]),
+                        Datum::Int(5)
+                    ]),
+                    Datum::List(vec![
+                        Datum::List(vec![
+                            Datum::Int(9),
+                            Datum::List(vec![Datum::Int(8), Datum::Int(7), Datum::Int(8)]),
+                            Datum::List(vec![]),
+                            Datum::List(vec![
+                                Datum::Int(2),
+                                Datum::Int(4),
+                                Datum::Int(10),
+                                Datum::Int(10)
+                            ]),
+                            Datum::List(vec![
+                                Datum::Int(2),
+                                Datum::Int(10),
+                                Datum::Int(8),
+                                Datum::Int(3),
+                                Datum::Int(3)
+                            ])
+                        ]),
+                        Datum::List(vec![]),
+                        Datum::List(vec![
+                            Datum::List(vec![Datum::Int(6), Datum::Int(1), Datum::Int(10)]),
+                            Datum::List(vec![]),
+                            Datum::Int(3),
+                            Datum::Int(6)
+                        ]),
+                        Datum::List(vec![Datum::Int(3)])
+                    ]),
+                    Datum::List(vec![]),
+                    Datum::List(vec![Datum::Int(4)]),
+                    Datum::List(vec![
+                        Datum::Int(3),
+                        Datum::Int(0),
+                        Datum::Int(1),
+                        Datum::Int(10)
+                    ])
+                ]))
+        );
+    }
+}
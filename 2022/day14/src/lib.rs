@@ -0,0 +1,71 @@
+use aoc::{Generator, ParseError, Rng, Solution};
+
+/// Add a path of rock, from 2 to 6 points joined by straight lines that turn at each point, below
+/// the sand coming in at 500,0.
+fn generate_path(rng: &mut Rng, input: &mut String) {
+    let (mut x, mut y) = (rng.between(450, 550), rng.between(60, 170));
+    let mut points = vec![format!("{},{}", x, y)];
+    let mut horizontal = rng.between(0, 1) == 1;
+    for _ in 1..rng.between(2, 6) {
+        let length = rng.between(1, 10);
+        let forwards = rng.between(0, 1) == 1;
+        let coordinate = if horizontal { &mut x } else { &mut y };
+        // Five turns of at most 10 can't take the path past 0 from where it starts.
+        *coordinate = if forwards {
+            *coordinate + length
+        } else {
+            *coordinate - length
+        };
+        points.push(format!("{},{}", x, y));
+        horizontal = !horizontal;
+    }
+    input.push_str(&points.join(" -> "));
+    input.push('\n');
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<String>;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        aoc::parse_lines(input, |line| Ok(line.to_string()))
+    }
+
+    /// Not solved yet, so there's no answer.
+    fn part1(_input: &Self::Input) -> Option<usize> {
+        None
+    }
+
+    /// Not solved yet, so there's no answer.
+    fn part2(_input: &Self::Input) -> Option<usize> {
+        None
+    }
+
+    fn generators() -> Vec<Generator> {
+        vec![Generator::new(
+            "random",
+            "Paths of rock with up to 6 points, around and below the sand's source",
+            |rng, size, input| {
+                while input.len() < size {
+                    generate_path(rng, input);
+                }
+            },
+        )]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day14;
+    use aoc::Solution;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn example_parses() {
+        assert!(Day14::parse(EXAMPLE).is_ok());
+    }
+}
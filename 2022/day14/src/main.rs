@@ -1,3 +1,3 @@
 fn main() {
-    println!("Hello, world!");
+    aoc::main::<day14::Day14>(14);
 }